@@ -0,0 +1,815 @@
+//! Enums shaped like the GL and Vulkan enums generated by bindgen.
+
+/// Sparse values, like the GL enums
+#[enum_ffi_newtype::enum_ffi(variants)]
+#[repr(u32)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GlEnum {
+    Gl0 = 0x8000,
+    Gl1 = 0x8004,
+    Gl2 = 0x8008,
+    Gl3 = 0x800c,
+    Gl4 = 0x8010,
+    Gl5 = 0x800f,
+    Gl6 = 0x8013,
+    Gl7 = 0x8017,
+    Gl8 = 0x801b,
+    Gl9 = 0x801f,
+    Gl10 = 0x801e,
+    Gl11 = 0x8022,
+    Gl12 = 0x8026,
+    Gl13 = 0x802a,
+    Gl14 = 0x802e,
+    Gl15 = 0x802d,
+    Gl16 = 0x8031,
+    Gl17 = 0x8035,
+    Gl18 = 0x8039,
+    Gl19 = 0x803d,
+    Gl20 = 0x803c,
+    Gl21 = 0x8040,
+    Gl22 = 0x8044,
+    Gl23 = 0x8048,
+    Gl24 = 0x804c,
+    Gl25 = 0x804b,
+    Gl26 = 0x804f,
+    Gl27 = 0x8053,
+    Gl28 = 0x8057,
+    Gl29 = 0x805b,
+    Gl30 = 0x805a,
+    Gl31 = 0x805e,
+    Gl32 = 0x8062,
+    Gl33 = 0x8066,
+    Gl34 = 0x806a,
+    Gl35 = 0x8069,
+    Gl36 = 0x806d,
+    Gl37 = 0x8071,
+    Gl38 = 0x8075,
+    Gl39 = 0x8079,
+    Gl40 = 0x8078,
+    Gl41 = 0x807c,
+    Gl42 = 0x8080,
+    Gl43 = 0x8084,
+    Gl44 = 0x8088,
+    Gl45 = 0x8087,
+    Gl46 = 0x808b,
+    Gl47 = 0x808f,
+    Gl48 = 0x8093,
+    Gl49 = 0x8097,
+    Gl50 = 0x8096,
+    Gl51 = 0x809a,
+    Gl52 = 0x809e,
+    Gl53 = 0x80a2,
+    Gl54 = 0x80a6,
+    Gl55 = 0x80a5,
+    Gl56 = 0x80a9,
+    Gl57 = 0x80ad,
+    Gl58 = 0x80b1,
+    Gl59 = 0x80b5,
+    Gl60 = 0x80b4,
+    Gl61 = 0x80b8,
+    Gl62 = 0x80bc,
+    Gl63 = 0x80c0,
+    Gl64 = 0x80c4,
+    Gl65 = 0x80c3,
+    Gl66 = 0x80c7,
+    Gl67 = 0x80cb,
+    Gl68 = 0x80cf,
+    Gl69 = 0x80d3,
+    Gl70 = 0x80d2,
+    Gl71 = 0x80d6,
+    Gl72 = 0x80da,
+    Gl73 = 0x80de,
+    Gl74 = 0x80e2,
+    Gl75 = 0x80e1,
+    Gl76 = 0x80e5,
+    Gl77 = 0x80e9,
+    Gl78 = 0x80ed,
+    Gl79 = 0x80f1,
+    Gl80 = 0x80f0,
+    Gl81 = 0x80f4,
+    Gl82 = 0x80f8,
+    Gl83 = 0x80fc,
+    Gl84 = 0x8100,
+    Gl85 = 0x80ff,
+    Gl86 = 0x8103,
+    Gl87 = 0x8107,
+    Gl88 = 0x810b,
+    Gl89 = 0x810f,
+    Gl90 = 0x810e,
+    Gl91 = 0x8112,
+    Gl92 = 0x8116,
+    Gl93 = 0x811a,
+    Gl94 = 0x811e,
+    Gl95 = 0x811d,
+    Gl96 = 0x8121,
+    Gl97 = 0x8125,
+    Gl98 = 0x8129,
+    Gl99 = 0x812d,
+    Gl100 = 0x812c,
+    Gl101 = 0x8130,
+    Gl102 = 0x8134,
+    Gl103 = 0x8138,
+    Gl104 = 0x813c,
+    Gl105 = 0x813b,
+    Gl106 = 0x813f,
+    Gl107 = 0x8143,
+    Gl108 = 0x8147,
+    Gl109 = 0x814b,
+    Gl110 = 0x814a,
+    Gl111 = 0x814e,
+    Gl112 = 0x8152,
+    Gl113 = 0x8156,
+    Gl114 = 0x815a,
+    Gl115 = 0x8159,
+    Gl116 = 0x815d,
+    Gl117 = 0x8161,
+    Gl118 = 0x8165,
+    Gl119 = 0x8169,
+    Gl120 = 0x8168,
+    Gl121 = 0x816c,
+    Gl122 = 0x8170,
+    Gl123 = 0x8174,
+    Gl124 = 0x8178,
+    Gl125 = 0x8177,
+    Gl126 = 0x817b,
+    Gl127 = 0x817f,
+    Gl128 = 0x8183,
+    Gl129 = 0x8187,
+    Gl130 = 0x8186,
+    Gl131 = 0x818a,
+    Gl132 = 0x818e,
+    Gl133 = 0x8192,
+    Gl134 = 0x8196,
+    Gl135 = 0x8195,
+    Gl136 = 0x8199,
+    Gl137 = 0x819d,
+    Gl138 = 0x81a1,
+    Gl139 = 0x81a5,
+    Gl140 = 0x81a4,
+    Gl141 = 0x81a8,
+    Gl142 = 0x81ac,
+    Gl143 = 0x81b0,
+    Gl144 = 0x81b4,
+    Gl145 = 0x81b3,
+    Gl146 = 0x81b7,
+    Gl147 = 0x81bb,
+    Gl148 = 0x81bf,
+    Gl149 = 0x81c3,
+    Gl150 = 0x81c2,
+    Gl151 = 0x81c6,
+    Gl152 = 0x81ca,
+    Gl153 = 0x81ce,
+    Gl154 = 0x81d2,
+    Gl155 = 0x81d1,
+    Gl156 = 0x81d5,
+    Gl157 = 0x81d9,
+    Gl158 = 0x81dd,
+    Gl159 = 0x81e1,
+    Gl160 = 0x81e0,
+    Gl161 = 0x81e4,
+    Gl162 = 0x81e8,
+    Gl163 = 0x81ec,
+    Gl164 = 0x81f0,
+    Gl165 = 0x81ef,
+    Gl166 = 0x81f3,
+    Gl167 = 0x81f7,
+    Gl168 = 0x81fb,
+    Gl169 = 0x81ff,
+    Gl170 = 0x81fe,
+    Gl171 = 0x8202,
+    Gl172 = 0x8206,
+    Gl173 = 0x820a,
+    Gl174 = 0x820e,
+    Gl175 = 0x820d,
+    Gl176 = 0x8211,
+    Gl177 = 0x8215,
+    Gl178 = 0x8219,
+    Gl179 = 0x821d,
+    Gl180 = 0x821c,
+    Gl181 = 0x8220,
+    Gl182 = 0x8224,
+    Gl183 = 0x8228,
+    Gl184 = 0x822c,
+    Gl185 = 0x822b,
+    Gl186 = 0x822f,
+    Gl187 = 0x8233,
+    Gl188 = 0x8237,
+    Gl189 = 0x823b,
+    Gl190 = 0x823a,
+    Gl191 = 0x823e,
+    Gl192 = 0x8242,
+    Gl193 = 0x8246,
+    Gl194 = 0x824a,
+    Gl195 = 0x8249,
+    Gl196 = 0x824d,
+    Gl197 = 0x8251,
+    Gl198 = 0x8255,
+    Gl199 = 0x8259,
+    Gl200 = 0x8258,
+    Gl201 = 0x825c,
+    Gl202 = 0x8260,
+    Gl203 = 0x8264,
+    Gl204 = 0x8268,
+    Gl205 = 0x8267,
+    Gl206 = 0x826b,
+    Gl207 = 0x826f,
+    Gl208 = 0x8273,
+    Gl209 = 0x8277,
+    Gl210 = 0x8276,
+    Gl211 = 0x827a,
+    Gl212 = 0x827e,
+    Gl213 = 0x8282,
+    Gl214 = 0x8286,
+    Gl215 = 0x8285,
+    Gl216 = 0x8289,
+    Gl217 = 0x828d,
+    Gl218 = 0x8291,
+    Gl219 = 0x8295,
+    Gl220 = 0x8294,
+    Gl221 = 0x8298,
+    Gl222 = 0x829c,
+    Gl223 = 0x82a0,
+    Gl224 = 0x82a4,
+    Gl225 = 0x82a3,
+    Gl226 = 0x82a7,
+    Gl227 = 0x82ab,
+    Gl228 = 0x82af,
+    Gl229 = 0x82b3,
+    Gl230 = 0x82b2,
+    Gl231 = 0x82b6,
+    Gl232 = 0x82ba,
+    Gl233 = 0x82be,
+    Gl234 = 0x82c2,
+    Gl235 = 0x82c1,
+    Gl236 = 0x82c5,
+    Gl237 = 0x82c9,
+    Gl238 = 0x82cd,
+    Gl239 = 0x82d1,
+    Gl240 = 0x82d0,
+    Gl241 = 0x82d4,
+    Gl242 = 0x82d8,
+    Gl243 = 0x82dc,
+    Gl244 = 0x82e0,
+    Gl245 = 0x82df,
+    Gl246 = 0x82e3,
+    Gl247 = 0x82e7,
+    Gl248 = 0x82eb,
+    Gl249 = 0x82ef,
+    Gl250 = 0x82ee,
+    Gl251 = 0x82f2,
+    Gl252 = 0x82f6,
+    Gl253 = 0x82fa,
+    Gl254 = 0x82fe,
+    Gl255 = 0x82fd,
+    Gl256 = 0x8301,
+    Gl257 = 0x8305,
+    Gl258 = 0x8309,
+    Gl259 = 0x830d,
+    Gl260 = 0x830c,
+    Gl261 = 0x8310,
+    Gl262 = 0x8314,
+    Gl263 = 0x8318,
+    Gl264 = 0x831c,
+    Gl265 = 0x831b,
+    Gl266 = 0x831f,
+    Gl267 = 0x8323,
+    Gl268 = 0x8327,
+    Gl269 = 0x832b,
+    Gl270 = 0x832a,
+    Gl271 = 0x832e,
+    Gl272 = 0x8332,
+    Gl273 = 0x8336,
+    Gl274 = 0x833a,
+    Gl275 = 0x8339,
+    Gl276 = 0x833d,
+    Gl277 = 0x8341,
+    Gl278 = 0x8345,
+    Gl279 = 0x8349,
+    Gl280 = 0x8348,
+    Gl281 = 0x834c,
+    Gl282 = 0x8350,
+    Gl283 = 0x8354,
+    Gl284 = 0x8358,
+    Gl285 = 0x8357,
+    Gl286 = 0x835b,
+    Gl287 = 0x835f,
+    Gl288 = 0x8363,
+    Gl289 = 0x8367,
+    Gl290 = 0x8366,
+    Gl291 = 0x836a,
+    Gl292 = 0x836e,
+    Gl293 = 0x8372,
+    Gl294 = 0x8376,
+    Gl295 = 0x8375,
+    Gl296 = 0x8379,
+    Gl297 = 0x837d,
+    Gl298 = 0x8381,
+    Gl299 = 0x8385,
+    Gl300 = 0x8384,
+    Gl301 = 0x8388,
+    Gl302 = 0x838c,
+    Gl303 = 0x8390,
+    Gl304 = 0x8394,
+    Gl305 = 0x8393,
+    Gl306 = 0x8397,
+    Gl307 = 0x839b,
+    Gl308 = 0x839f,
+    Gl309 = 0x83a3,
+    Gl310 = 0x83a2,
+    Gl311 = 0x83a6,
+    Gl312 = 0x83aa,
+    Gl313 = 0x83ae,
+    Gl314 = 0x83b2,
+    Gl315 = 0x83b1,
+    Gl316 = 0x83b5,
+    Gl317 = 0x83b9,
+    Gl318 = 0x83bd,
+    Gl319 = 0x83c1,
+    Gl320 = 0x83c0,
+    Gl321 = 0x83c4,
+    Gl322 = 0x83c8,
+    Gl323 = 0x83cc,
+    Gl324 = 0x83d0,
+    Gl325 = 0x83cf,
+    Gl326 = 0x83d3,
+    Gl327 = 0x83d7,
+    Gl328 = 0x83db,
+    Gl329 = 0x83df,
+    Gl330 = 0x83de,
+    Gl331 = 0x83e2,
+    Gl332 = 0x83e6,
+    Gl333 = 0x83ea,
+    Gl334 = 0x83ee,
+    Gl335 = 0x83ed,
+    Gl336 = 0x83f1,
+    Gl337 = 0x83f5,
+    Gl338 = 0x83f9,
+    Gl339 = 0x83fd,
+    Gl340 = 0x83fc,
+    Gl341 = 0x8400,
+    Gl342 = 0x8404,
+    Gl343 = 0x8408,
+    Gl344 = 0x840c,
+    Gl345 = 0x840b,
+    Gl346 = 0x840f,
+    Gl347 = 0x8413,
+    Gl348 = 0x8417,
+    Gl349 = 0x841b,
+    Gl350 = 0x841a,
+    Gl351 = 0x841e,
+    Gl352 = 0x8422,
+    Gl353 = 0x8426,
+    Gl354 = 0x842a,
+    Gl355 = 0x8429,
+    Gl356 = 0x842d,
+    Gl357 = 0x8431,
+    Gl358 = 0x8435,
+    Gl359 = 0x8439,
+    Gl360 = 0x8438,
+    Gl361 = 0x843c,
+    Gl362 = 0x8440,
+    Gl363 = 0x8444,
+    Gl364 = 0x8448,
+    Gl365 = 0x8447,
+    Gl366 = 0x844b,
+    Gl367 = 0x844f,
+    Gl368 = 0x8453,
+    Gl369 = 0x8457,
+    Gl370 = 0x8456,
+    Gl371 = 0x845a,
+    Gl372 = 0x845e,
+    Gl373 = 0x8462,
+    Gl374 = 0x8466,
+    Gl375 = 0x8465,
+    Gl376 = 0x8469,
+    Gl377 = 0x846d,
+    Gl378 = 0x8471,
+    Gl379 = 0x8475,
+    Gl380 = 0x8474,
+    Gl381 = 0x8478,
+    Gl382 = 0x847c,
+    Gl383 = 0x8480,
+    Gl384 = 0x8484,
+    Gl385 = 0x8483,
+    Gl386 = 0x8487,
+    Gl387 = 0x848b,
+    Gl388 = 0x848f,
+    Gl389 = 0x8493,
+    Gl390 = 0x8492,
+    Gl391 = 0x8496,
+    Gl392 = 0x849a,
+    Gl393 = 0x849e,
+    Gl394 = 0x84a2,
+    Gl395 = 0x84a1,
+    Gl396 = 0x84a5,
+    Gl397 = 0x84a9,
+    Gl398 = 0x84ad,
+    Gl399 = 0x84b1,
+}
+
+/// Consecutive values, like the Vulkan formats
+#[enum_ffi_newtype::enum_ffi(variants)]
+#[repr(i32)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum VkFormat {
+    Format0,
+    Format1,
+    Format2,
+    Format3,
+    Format4,
+    Format5,
+    Format6,
+    Format7,
+    Format8,
+    Format9,
+    Format10,
+    Format11,
+    Format12,
+    Format13,
+    Format14,
+    Format15,
+    Format16,
+    Format17,
+    Format18,
+    Format19,
+    Format20,
+    Format21,
+    Format22,
+    Format23,
+    Format24,
+    Format25,
+    Format26,
+    Format27,
+    Format28,
+    Format29,
+    Format30,
+    Format31,
+    Format32,
+    Format33,
+    Format34,
+    Format35,
+    Format36,
+    Format37,
+    Format38,
+    Format39,
+    Format40,
+    Format41,
+    Format42,
+    Format43,
+    Format44,
+    Format45,
+    Format46,
+    Format47,
+    Format48,
+    Format49,
+    Format50,
+    Format51,
+    Format52,
+    Format53,
+    Format54,
+    Format55,
+    Format56,
+    Format57,
+    Format58,
+    Format59,
+    Format60,
+    Format61,
+    Format62,
+    Format63,
+    Format64,
+    Format65,
+    Format66,
+    Format67,
+    Format68,
+    Format69,
+    Format70,
+    Format71,
+    Format72,
+    Format73,
+    Format74,
+    Format75,
+    Format76,
+    Format77,
+    Format78,
+    Format79,
+    Format80,
+    Format81,
+    Format82,
+    Format83,
+    Format84,
+    Format85,
+    Format86,
+    Format87,
+    Format88,
+    Format89,
+    Format90,
+    Format91,
+    Format92,
+    Format93,
+    Format94,
+    Format95,
+    Format96,
+    Format97,
+    Format98,
+    Format99,
+    Format100,
+    Format101,
+    Format102,
+    Format103,
+    Format104,
+    Format105,
+    Format106,
+    Format107,
+    Format108,
+    Format109,
+    Format110,
+    Format111,
+    Format112,
+    Format113,
+    Format114,
+    Format115,
+    Format116,
+    Format117,
+    Format118,
+    Format119,
+    Format120,
+    Format121,
+    Format122,
+    Format123,
+    Format124,
+    Format125,
+    Format126,
+    Format127,
+    Format128,
+    Format129,
+    Format130,
+    Format131,
+    Format132,
+    Format133,
+    Format134,
+    Format135,
+    Format136,
+    Format137,
+    Format138,
+    Format139,
+    Format140,
+    Format141,
+    Format142,
+    Format143,
+    Format144,
+    Format145,
+    Format146,
+    Format147,
+    Format148,
+    Format149,
+    Format150,
+    Format151,
+    Format152,
+    Format153,
+    Format154,
+    Format155,
+    Format156,
+    Format157,
+    Format158,
+    Format159,
+    Format160,
+    Format161,
+    Format162,
+    Format163,
+    Format164,
+    Format165,
+    Format166,
+    Format167,
+    Format168,
+    Format169,
+    Format170,
+    Format171,
+    Format172,
+    Format173,
+    Format174,
+    Format175,
+    Format176,
+    Format177,
+    Format178,
+    Format179,
+    Format180,
+    Format181,
+    Format182,
+    Format183,
+    Format184,
+    Format185,
+    Format186,
+    Format187,
+    Format188,
+    Format189,
+    Format190,
+    Format191,
+    Format192,
+    Format193,
+    Format194,
+    Format195,
+    Format196,
+    Format197,
+    Format198,
+    Format199,
+    Format200,
+    Format201,
+    Format202,
+    Format203,
+    Format204,
+    Format205,
+    Format206,
+    Format207,
+    Format208,
+    Format209,
+    Format210,
+    Format211,
+    Format212,
+    Format213,
+    Format214,
+    Format215,
+    Format216,
+    Format217,
+    Format218,
+    Format219,
+    Format220,
+    Format221,
+    Format222,
+    Format223,
+    Format224,
+    Format225,
+    Format226,
+    Format227,
+    Format228,
+    Format229,
+    Format230,
+    Format231,
+    Format232,
+    Format233,
+    Format234,
+    Format235,
+    Format236,
+    Format237,
+    Format238,
+    Format239,
+    Format240,
+    Format241,
+    Format242,
+    Format243,
+    Format244,
+    Format245,
+    Format246,
+    Format247,
+    Format248,
+    Format249,
+    Format250,
+    Format251,
+    Format252,
+    Format253,
+    Format254,
+    Format255,
+    Format256,
+    Format257,
+    Format258,
+    Format259,
+    Format260,
+    Format261,
+    Format262,
+    Format263,
+    Format264,
+    Format265,
+    Format266,
+    Format267,
+    Format268,
+    Format269,
+    Format270,
+    Format271,
+    Format272,
+    Format273,
+    Format274,
+    Format275,
+    Format276,
+    Format277,
+    Format278,
+    Format279,
+    Format280,
+    Format281,
+    Format282,
+    Format283,
+    Format284,
+    Format285,
+    Format286,
+    Format287,
+    Format288,
+    Format289,
+    Format290,
+    Format291,
+    Format292,
+    Format293,
+    Format294,
+    Format295,
+    Format296,
+    Format297,
+    Format298,
+    Format299,
+    Format300,
+    Format301,
+    Format302,
+    Format303,
+    Format304,
+    Format305,
+    Format306,
+    Format307,
+    Format308,
+    Format309,
+    Format310,
+    Format311,
+    Format312,
+    Format313,
+    Format314,
+    Format315,
+    Format316,
+    Format317,
+    Format318,
+    Format319,
+    Format320,
+    Format321,
+    Format322,
+    Format323,
+    Format324,
+    Format325,
+    Format326,
+    Format327,
+    Format328,
+    Format329,
+    Format330,
+    Format331,
+    Format332,
+    Format333,
+    Format334,
+    Format335,
+    Format336,
+    Format337,
+    Format338,
+    Format339,
+    Format340,
+    Format341,
+    Format342,
+    Format343,
+    Format344,
+    Format345,
+    Format346,
+    Format347,
+    Format348,
+    Format349,
+    Format350,
+    Format351,
+    Format352,
+    Format353,
+    Format354,
+    Format355,
+    Format356,
+    Format357,
+    Format358,
+    Format359,
+    Format360,
+    Format361,
+    Format362,
+    Format363,
+    Format364,
+    Format365,
+    Format366,
+    Format367,
+    Format368,
+    Format369,
+    Format370,
+    Format371,
+    Format372,
+    Format373,
+    Format374,
+    Format375,
+    Format376,
+    Format377,
+    Format378,
+    Format379,
+    Format380,
+    Format381,
+    Format382,
+    Format383,
+    Format384,
+    Format385,
+    Format386,
+    Format387,
+    Format388,
+    Format389,
+    Format390,
+    Format391,
+    Format392,
+    Format393,
+    Format394,
+    Format395,
+    Format396,
+    Format397,
+    Format398,
+    Format399,
+}
@@ -0,0 +1,49 @@
+//! Compares the generated conversions to the rustified enum with a linear comparison of
+//! every constant, which is how they used to be implemented.
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use enums::{GlEnum, GlEnumRustified, VkFormat, VkFormatRustified};
+
+mod enums;
+
+/// Known values from all over the enum, and some unknown ones.
+fn inputs(known: &[u32]) -> Vec<u32> {
+    known.iter().step_by(7).flat_map(|&value| [value, value.wrapping_add(1) | 0x10000]).collect()
+}
+
+fn linear_gl(value: GlEnum) -> Option<GlEnumRustified> {
+    GlEnum::KNOWN.iter().position(|known| known.0 == value.0).map(|index| GlEnumRustified::ALL[index])
+}
+
+fn linear_vk(value: VkFormat) -> Option<VkFormatRustified> {
+    VkFormat::KNOWN.iter().position(|known| known.0 == value.0).map(|index| VkFormatRustified::ALL[index])
+}
+
+fn sparse(c: &mut Criterion) {
+    let known: Vec<_> = GlEnum::KNOWN.iter().map(|value| value.0).collect();
+    let inputs: Vec<_> = inputs(&known).into_iter().map(GlEnum).collect();
+    let mut group = c.benchmark_group("sparse");
+    group.bench_function("generated", |b| b.iter(|| {
+        inputs.iter().filter(|&&value| black_box(value).try_to_rust().is_some()).count()
+    }));
+    group.bench_function("linear", |b| b.iter(|| {
+        inputs.iter().filter(|&&value| linear_gl(black_box(value)).is_some()).count()
+    }));
+    group.finish();
+}
+
+fn dense(c: &mut Criterion) {
+    let known: Vec<_> = VkFormat::KNOWN.iter().map(|value| value.0 as u32).collect();
+    let inputs: Vec<_> = inputs(&known).into_iter().map(|value| VkFormat(value as i32)).collect();
+    let mut group = c.benchmark_group("dense");
+    group.bench_function("generated", |b| b.iter(|| {
+        inputs.iter().filter(|&&value| black_box(value).try_to_rust().is_some()).count()
+    }));
+    group.bench_function("linear", |b| b.iter(|| {
+        inputs.iter().filter(|&&value| linear_vk(black_box(value)).is_some()).count()
+    }));
+    group.finish();
+}
+
+criterion_group!(benches, sparse, dense);
+criterion_main!(benches);
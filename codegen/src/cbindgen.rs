@@ -0,0 +1,80 @@
+//! Output shaped for cbindgen, which exports the FFI enums to a C header.
+//!
+//! cbindgen reads the expanded crate (`parse.expand`), where the FFI enum is a
+//! `#[repr(transparent)]` newtype that becomes `typedef uint32_t Foo;`. The associated constants
+//! would be exported as `Foo_A`, and the variant tables not at all, so they are hidden from
+//! cbindgen with `cbindgen:ignore`. Free constants with the configured names are exported instead.
+
+use darling::FromMeta;
+use heck::ToShoutySnakeCase;
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::ext::IdentExt;
+use syn::{Ident, Visibility};
+use crate::FfiVariant;
+
+/// The options of `cbindgen`, e.g. `cbindgen(prefix = "FOO_")`
+#[derive(Debug, Default, FromMeta)]
+#[darling(from_word = || Ok(Self::default()))]
+pub(crate) struct CbindgenArgs {
+    /// The prefix of the exported constants, where `{}` is replaced by the name of the enum
+    ///
+    /// Defaults to `{}_`, which matches the names cbindgen gives associated constants.
+    prefix: Option<String>,
+    /// Convert the names of the exported constants to SCREAMING_SNAKE_CASE, e.g. `FOO_BAR_A`
+    /// for the variant `A` of `FooBar`
+    #[darling(default)]
+    screaming_snake_case: bool,
+}
+
+impl CbindgenArgs {
+    /// The name of the exported constant of `variant`.
+    pub(crate) fn constant_name(&self, enum_ident: &Ident, variant: &Ident) -> Ident {
+        let prefix = self.prefix.as_deref().unwrap_or("{}_").replace("{}", &enum_ident.unraw().to_string());
+        let name = format!("{prefix}{}", variant.unraw());
+        if self.screaming_snake_case {
+            format_ident!("{}", name.to_shouty_snake_case())
+        } else {
+            format_ident!("{}", name)
+        }
+    }
+
+    /// The free constant of `variant`, or of its alias `alias`, which cbindgen exports.
+    ///
+    /// cbindgen can't evaluate the `NonZero` constructor, so `non_zero` constants have the
+    /// integer type instead of the FFI enum.
+    pub(crate) fn constant(
+        &self,
+        enum_ident: &Ident,
+        vis: &Visibility,
+        base_repr_tokens: &TokenStream,
+        non_zero: bool,
+        variant: &FfiVariant,
+        alias: Option<&Ident>,
+    ) -> TokenStream {
+        let FfiVariant { ident, cfg, doc, discriminant, .. } = variant;
+        let name = self.constant_name(enum_ident, alias.unwrap_or(ident));
+        let doc = match alias {
+            Some(_) => format!("Alias of `{}`.", self.constant_name(enum_ident, ident)),
+            None => doc.clone(),
+        };
+        let doc = doc.lines().map(|line| if line.is_empty() { String::new() } else { format!(" {line}") });
+        let (ty, value) = if non_zero {
+            (base_repr_tokens.clone(), quote! { #discriminant })
+        } else {
+            (quote! { #enum_ident }, quote! { #enum_ident(#discriminant) })
+        };
+        quote! {
+            #cfg
+            #( #[doc = #doc] )*
+            #[doc(hidden)]
+            #[allow(non_upper_case_globals)]
+            #vis const #name: #ty = #value;
+        }
+    }
+}
+
+/// The attribute hiding a generated item from cbindgen.
+pub(crate) fn ignore() -> TokenStream {
+    quote! { #[doc = "cbindgen:ignore"] }
+}
@@ -0,0 +1,195 @@
+//! The conversions from the FFI enum and raw values to the rustified enum.
+
+use std::iter;
+use proc_macro2::TokenStream;
+use quote::quote;
+use crate::discriminant::Discriminant;
+use crate::FfiEnum;
+
+/// Enums with more variants than this look up sparse values in a sorted table, rather than
+/// matching on them.
+const MATCH_LIMIT: usize = 64;
+
+/// How a raw value is mapped to its variant.
+enum Strategy {
+    /// Compare against every constant in turn, if some discriminants are only known to the compiler
+    Guards,
+    /// Check that the value lies in a range without gaps, and reinterpret it as the rustified enum
+    Cast { min: i128, max: i128 },
+    /// Match on the raw value
+    Match,
+    /// Binary search the raw value in a sorted table
+    Table,
+}
+
+impl Strategy {
+    fn select(ffi_enum: &FfiEnum) -> Strategy {
+        let mut values = Vec::with_capacity(ffi_enum.variants.len());
+        for variant in &ffi_enum.variants {
+            match variant.discriminant {
+                Discriminant::Value(value) => values.push(value),
+                Discriminant::Expr { .. } => return Strategy::Guards,
+            }
+        }
+        // Configured out variants would leave gaps in the range or the table, which only a
+        // match can skip.
+        if ffi_enum.variants.iter().any(|variant| !variant.cfg.is_empty()) {
+            return Strategy::Match;
+        }
+        // The cast is only sound if the rustified enum has the layout of its discriminant, and
+        // every value is the discriminant of its variant.
+        let fieldless = !ffi_enum.catch_all.as_ref().is_some_and(|catch_all| catch_all.keep_value);
+        let also = ffi_enum.variants.iter().flat_map(|variant| &variant.also);
+        let single_values = also.clone().next().is_none();
+        values.extend(also);
+        if let (Some(&min), Some(&max), true) = (values.iter().min(), values.iter().max(), single_values) {
+            // Discriminants are distinct, so there are no gaps if the range is as long as the list.
+            if fieldless && max - min + 1 == values.len() as i128 {
+                return Strategy::Cast { min, max };
+            }
+        }
+        if values.len() <= MATCH_LIMIT {
+            Strategy::Match
+        } else {
+            Strategy::Table
+        }
+    }
+}
+
+/// Generates the const conversions to the rustified enum, with a lookup strategy chosen from
+/// the discriminants.
+pub(crate) fn to_rust_impls(ffi_enum: &FfiEnum) -> TokenStream {
+    let FfiEnum { original_ident, rust_enum_ident, vis, base_repr_tokens, variants, .. } = ffi_enum;
+    let variant_idents: Vec<_> = variants.iter().map(|variant| &variant.ident).collect();
+    let rust_idents: Vec<_> = variants.iter().map(|variant| &variant.rust_ident).collect();
+    let variant_cfgs: Vec<_> = variants.iter().map(|variant| &variant.cfg).collect();
+    let raw_self = ffi_enum.raw_value(quote! { self });
+    // Every raw value which converts to a variant, and whether it is an `also` value.
+    let entries: Vec<_> = variants.iter()
+        .flat_map(|variant| {
+            let also = variant.also.iter().map(move |&value| (Discriminant::Value(value), variant, true));
+            iter::once((variant.discriminant.clone(), variant, false)).chain(also)
+        })
+        .collect();
+
+    let body = match Strategy::select(ffi_enum) {
+        Strategy::Guards => {
+            let raw_constants = variant_idents.iter().map(|ident| ffi_enum.raw_value(quote! { #original_ident::#ident }));
+            let also_values = entries.iter().filter(|(_, _, also)| *also).map(|(value, _, _)| value);
+            let also_cfgs = entries.iter().filter(|(_, _, also)| *also).map(|(_, variant, _)| &variant.cfg);
+            let also_idents = entries.iter().filter(|(_, _, also)| *also).map(|(_, variant, _)| &variant.rust_ident);
+            quote! {
+                match #raw_self {
+                    #( #variant_cfgs raw if raw == #raw_constants => Some(#rust_enum_ident::#rust_idents), )*
+                    #( #also_cfgs #also_values => Some(#rust_enum_ident::#also_idents), )*
+                    _ => None,
+                }
+            }
+        }
+        Strategy::Cast { min, max } => {
+            let (min, max) = (Discriminant::Value(min), Discriminant::Value(max));
+            quote! {
+                let raw = #raw_self;
+                if #min <= raw && raw <= #max {
+                    // SAFETY: The rustified enum is fieldless with the same `repr` as the raw value,
+                    // and every value in this range is the discriminant of one of its variants.
+                    Some(unsafe { core::mem::transmute::<#base_repr_tokens, #rust_enum_ident>(raw) })
+                } else {
+                    None
+                }
+            }
+        }
+        Strategy::Match => {
+            let discriminants = entries.iter().map(|(discriminant, _, _)| discriminant);
+            let cfgs = entries.iter().map(|(_, variant, _)| &variant.cfg);
+            let idents = entries.iter().map(|(_, variant, _)| &variant.rust_ident);
+            quote! {
+                match #raw_self {
+                    #( #cfgs #discriminants => Some(#rust_enum_ident::#idents), )*
+                    _ => None,
+                }
+            }
+        }
+        Strategy::Table => {
+            let mut sorted = entries;
+            sorted.sort_by_key(|(discriminant, _, _)| match discriminant {
+                Discriminant::Value(value) => *value,
+                Discriminant::Expr { .. } => unreachable!(),
+            });
+            let discriminants = sorted.iter().map(|(discriminant, _, _)| discriminant);
+            let sorted_idents = sorted.iter().map(|(_, variant, _)| &variant.rust_ident);
+            let indices = 0..sorted.len();
+            // `slice::binary_search` isn't const.
+            quote! {
+                const RAW: &[#base_repr_tokens] = &[#(#discriminants),*];
+                let raw = #raw_self;
+                let mut found = None;
+                let (mut low, mut high) = (0, RAW.len());
+                while low < high {
+                    let mid = low + (high - low) / 2;
+                    if RAW[mid] < raw {
+                        low = mid + 1;
+                    } else if RAW[mid] > raw {
+                        high = mid;
+                    } else {
+                        found = Some(mid);
+                        break;
+                    }
+                }
+                match found {
+                    #( Some(#indices) => Some(#rust_enum_ident::#sorted_idents), )*
+                    _ => None,
+                }
+            }
+        }
+    };
+
+    let to_rust = ffi_enum.catch_all.as_ref().map(|catch_all| {
+        let catch_all_ident = &catch_all.ident;
+        let catch_all_value = if catch_all.keep_value {
+            quote! { #rust_enum_ident::#catch_all_ident(self.0) }
+        } else {
+            quote! { #rust_enum_ident::#catch_all_ident }
+        };
+        quote! {
+            /// Converts to the rust enum, mapping values without a dedicated variant to the
+            /// catch-all variant.
+            #vis const fn to_rust(self) -> #rust_enum_ident {
+                match self.try_to_rust() {
+                    Some(value) => value,
+                    None => #catch_all_value,
+                }
+            }
+        }
+    });
+    let newtype_from_raw = if ffi_enum.non_zero {
+        quote! {
+            let Some(raw) = core::num::NonZero::new(raw) else {
+                return None;
+            };
+            #original_ident(raw)
+        }
+    } else {
+        quote! { #original_ident(raw) }
+    };
+
+    quote! {
+        #[allow(deprecated)]
+        impl #original_ident {
+            /// Converts to the rust enum, or `None` for values without a dedicated variant.
+            #vis const fn try_to_rust(self) -> Option<#rust_enum_ident> {
+                #body
+            }
+
+            #to_rust
+        }
+
+        impl #rust_enum_ident {
+            /// The variant with the raw value `raw`, if there is a dedicated one.
+            #vis const fn from_repr(raw: #base_repr_tokens) -> Option<Self> {
+                let value = { #newtype_from_raw };
+                value.try_to_rust()
+            }
+        }
+    }
+}
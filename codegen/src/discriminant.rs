@@ -0,0 +1,440 @@
+//! Evaluation of enum discriminant expressions.
+
+use std::fmt;
+use proc_macro2::{Literal, Span, TokenStream};
+use quote::{quote, ToTokens};
+use syn::spanned::Spanned;
+use syn::visit_mut::VisitMut;
+use syn::punctuated::Punctuated;
+use syn::{parse_quote, Attribute, BinOp, Expr, ExprLit, ExprPath, Ident, Lit, LitInt, Meta, Token, UnOp, Variant};
+use crate::repr::IntType;
+
+/// The discriminant of a variant, as far as the macro can determine it.
+#[derive(Debug, Clone)]
+pub(crate) enum Discriminant {
+    /// The discriminant could be folded to a constant during macro expansion.
+    Value(i128),
+    /// The discriminant depends on items the macro can't see, e.g. constants generated by
+    /// bindgen, so evaluating it is left to the compiler.
+    ///
+    /// `offset` counts the implicit increments since the last explicit discriminant.
+    Expr { expr: Expr, offset: u64 },
+}
+
+impl Discriminant {
+    pub(crate) fn is_zero(&self) -> bool {
+        matches!(self, Discriminant::Value(0))
+    }
+
+    /// The discriminant of an implicitly numbered variant following this one.
+    ///
+    /// Returns `None` if the value overflows.
+    fn successor(&self) -> Option<Discriminant> {
+        match self {
+            Discriminant::Value(value) => value.checked_add(1).map(Discriminant::Value),
+            Discriminant::Expr { expr, offset } => Some(Discriminant::Expr {
+                expr: expr.clone(),
+                offset: offset + 1,
+            }),
+        }
+    }
+
+    /// Selects `if_true` or `if_false`, depending on the cfg `predicate`.
+    fn conditional(predicate: &TokenStream, if_true: Discriminant, if_false: Discriminant) -> Discriminant {
+        match (&if_true, &if_false) {
+            (Discriminant::Value(a), Discriminant::Value(b)) if a == b => if_true,
+            _ => Discriminant::Expr {
+                expr: parse_quote! { if cfg!(#predicate) { #if_true } else { #if_false } },
+                offset: 0,
+            },
+        }
+    }
+}
+
+impl fmt::Display for Discriminant {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Discriminant::Value(value) => write!(f, "{value}"),
+            Discriminant::Expr { expr, offset: 0 } => write!(f, "{}", expr.to_token_stream()),
+            Discriminant::Expr { expr, offset } => write!(f, "({}) + {offset}", expr.to_token_stream()),
+        }
+    }
+}
+
+impl ToTokens for Discriminant {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        match self {
+            Discriminant::Value(value) => Literal::i128_unsuffixed(*value).to_tokens(tokens),
+            Discriminant::Expr { expr, offset: 0 } => expr.to_tokens(tokens),
+            Discriminant::Expr { expr, offset } => {
+                let offset = Literal::u64_unsuffixed(*offset);
+                tokens.extend(quote! { (#expr) + #offset })
+            }
+        }
+    }
+}
+
+/// Evaluates the discriminants of an enum, one variant at a time.
+pub(crate) struct DiscriminantEvaluator<'a> {
+    enum_ident: &'a Ident,
+    /// The type of the discriminants, if it is known.
+    ///
+    /// Without it, values are not range checked and only casts can truncate them.
+    repr: Option<IntType>,
+    discriminants: Vec<(Ident, Discriminant)>,
+    /// The discriminant of the next variant, if it is numbered implicitly.
+    ///
+    /// This depends on the cfg of the previous variants, since variants which are configured
+    /// out don't take part in the numbering. `None` if the value overflowed.
+    next_implicit: Option<Discriminant>,
+}
+
+impl<'a> DiscriminantEvaluator<'a> {
+    pub(crate) fn new(enum_ident: &'a Ident, repr: Option<IntType>) -> Self {
+        DiscriminantEvaluator {
+            enum_ident,
+            repr,
+            discriminants: vec![],
+            next_implicit: Some(Discriminant::Value(0)),
+        }
+    }
+
+    /// Evaluates the discriminant of the next variant of the enum.
+    ///
+    /// Expressions which can't be folded are kept for the compiler to evaluate, with
+    /// references to earlier variants (`Self::Other as u32`) replaced by their discriminant.
+    pub(crate) fn push_variant(&mut self, variant: &Variant) -> Result<&Discriminant, syn::Error> {
+        let discriminant = if let Some((_, expr)) = &variant.discriminant {
+            self.evaluate(expr)?
+        } else {
+            match &self.next_implicit {
+                None => return Err(syn::Error::new(variant.span(), "enum discriminant overflowed")),
+                Some(Discriminant::Value(value)) => {
+                    if let Some(repr) = self.repr.filter(|repr| !repr.contains(*value)) {
+                        return Err(syn::Error::new(
+                            variant.span(),
+                            format!("discriminant `{value}` of this variant is out of range for `{repr}`"),
+                        ));
+                    }
+                    Discriminant::Value(*value)
+                }
+                Some(discriminant) => discriminant.clone(),
+            }
+        };
+        let successor = discriminant.successor();
+        self.next_implicit = match cfg_predicate(&variant.attrs)? {
+            None => successor,
+            Some(predicate) => match (successor, self.next_implicit.take()) {
+                (Some(if_present), Some(if_absent)) => Some(Discriminant::conditional(&predicate, if_present, if_absent)),
+                _ => None,
+            },
+        };
+        self.discriminants.push((variant.ident.clone(), discriminant));
+        Ok(&self.discriminants.last().unwrap().1)
+    }
+
+    pub(crate) fn discriminants(&self) -> &[(Ident, Discriminant)] {
+        &self.discriminants
+    }
+
+    /// Picks a discriminant for an injected variant, which doesn't collide with any other variant
+    /// or with the `reserved` values, e.g. the `also` values of the variants.
+    ///
+    /// With `non_zero`, zero is never picked, since it has no FFI value. Returns `None` if some
+    /// discriminants are only known to the compiler, in which case the injected variant has to be
+    /// numbered implicitly.
+    pub(crate) fn unused_value(&self, span: Span, reserved: &[i128], non_zero: bool) -> Result<Option<i128>, syn::Error> {
+        let mut values = reserved.to_vec();
+        for (_, discriminant) in &self.discriminants {
+            match discriminant {
+                Discriminant::Value(value) => values.push(*value),
+                Discriminant::Expr { .. } => return Ok(None),
+            }
+        }
+        let is_free = |value: &i128| !(values.contains(value) || non_zero && *value == 0);
+        let in_range = |value: &i128| self.repr.is_none_or(|repr| repr.contains(*value));
+        let next = match values.iter().max() {
+            Some(max) => max.checked_add(1),
+            None => Some(0),
+        };
+        if let Some(next) = next.filter(|next| is_free(next) && in_range(next)) {
+            return Ok(Some(next));
+        }
+        let (min, max) = match self.repr {
+            Some(repr) => (repr.min(), repr.max()),
+            None => (i128::MIN, i128::MAX),
+        };
+        (min..=max)
+            .find(is_free)
+            .map(Some)
+            .ok_or_else(|| match self.repr {
+                Some(repr) => syn::Error::new(span, format!("no discriminant of `{repr}` left for the catch-all variant")),
+                None => syn::Error::new(span, "no discriminant left for the catch-all variant"),
+            })
+    }
+
+    fn evaluate(&self, expr: &Expr) -> Result<Discriminant, syn::Error> {
+        if let Some(value) = self.fold(expr, self.repr)? {
+            self.check_range(value, self.repr, expr.span())?;
+            return Ok(Discriminant::Value(value));
+        }
+        let mut expr = expr.clone();
+        let mut substitution = VariantSubstitution { evaluator: self, error: None };
+        substitution.visit_expr_mut(&mut expr);
+        match substitution.error {
+            Some(err) => Err(err),
+            None => Ok(Discriminant::Expr { expr, offset: 0 }),
+        }
+    }
+
+    /// Folds `expr` into a constant of type `ty`, mirroring the checks of the compiler.
+    ///
+    /// Returns `Ok(None)` if the expression is valid, but depends on something the macro
+    /// can't evaluate.
+    fn fold(&self, expr: &Expr, ty: Option<IntType>) -> Result<Option<i128>, syn::Error> {
+        let value = match expr {
+            Expr::Lit(ExprLit { lit: Lit::Int(lit_int), .. }) => return self.fold_int_lit(lit_int, ty, false),
+            Expr::Lit(ExprLit { lit: Lit::Byte(byte), .. }) => {
+                self.check_type(IntType::U8, ty, byte.span())?;
+                i128::from(byte.value())
+            }
+            Expr::Lit(lit) => return Err(syn::Error::new(lit.span(), "discriminant must be an integer")),
+            Expr::Paren(paren) => return self.fold(&paren.expr, ty),
+            Expr::Group(group) => return self.fold(&group.expr, ty),
+            Expr::Unary(unary) => {
+                // Negative literals are range checked as a whole, so that `-128` fits into `i8`.
+                if let (UnOp::Neg(_), Expr::Lit(ExprLit { lit: Lit::Int(lit_int), .. })) = (unary.op, &*unary.expr) {
+                    return self.fold_int_lit(lit_int, ty, true);
+                }
+                let Some(value) = self.fold(&unary.expr, ty)? else {
+                    return Ok(None);
+                };
+                let value = match (unary.op, ty) {
+                    (UnOp::Neg(_), _) => value.checked_neg().ok_or(syn::Error::new(
+                        unary.span(),
+                        "arithmetic overflow in discriminant",
+                    ))?,
+                    (UnOp::Not(_), Some(ty)) if ty.is_signed() => !value,
+                    // For unsigned types, the result of `!` depends on the width of the type.
+                    (UnOp::Not(_), Some(ty)) => match ty.wrap(!value) {
+                        Some(value) if value >= 0 => value,
+                        _ => return Ok(None),
+                    },
+                    _ => return Ok(None),
+                };
+                self.check_range(value, ty, unary.span())?;
+                value
+            }
+            Expr::Binary(binary) => {
+                let is_shift = matches!(binary.op, BinOp::Shl(_) | BinOp::Shr(_));
+                // The right-hand side of a shift may have any integer type.
+                let rhs_ty = if is_shift { self.operand_type(&binary.right) } else { ty };
+                let (Some(lhs), Some(rhs)) = (self.fold(&binary.left, ty)?, self.fold(&binary.right, rhs_ty)?) else {
+                    return Ok(None);
+                };
+                if is_shift {
+                    let bits = match ty {
+                        Some(ty) => match ty.bits() {
+                            Some(bits) => bits,
+                            None => return Ok(None),
+                        },
+                        None => 128,
+                    };
+                    if rhs < 0 || rhs >= i128::from(bits) {
+                        return Err(syn::Error::new(binary.span(), "attempt to shift with overflow"));
+                    }
+                    let rhs = rhs as u32;
+                    let shifted = if matches!(binary.op, BinOp::Shl(_)) { lhs << rhs } else { lhs >> rhs };
+                    match ty {
+                        Some(ty) => match ty.wrap(shifted) {
+                            Some(value) => value,
+                            None => return Ok(None),
+                        },
+                        None => shifted,
+                    }
+                } else {
+                    let value = match binary.op {
+                        BinOp::Add(_) => lhs.checked_add(rhs),
+                        BinOp::Sub(_) => lhs.checked_sub(rhs),
+                        BinOp::Mul(_) => lhs.checked_mul(rhs),
+                        BinOp::Div(_) | BinOp::Rem(_) if rhs == 0 => {
+                            return Err(syn::Error::new(binary.span(), "attempt to divide by zero"));
+                        }
+                        BinOp::Div(_) => lhs.checked_div(rhs),
+                        BinOp::Rem(_) => lhs.checked_rem(rhs),
+                        BinOp::BitAnd(_) => Some(lhs & rhs),
+                        BinOp::BitOr(_) => Some(lhs | rhs),
+                        BinOp::BitXor(_) => Some(lhs ^ rhs),
+                        // Comparisons and boolean operators don't produce an integer.
+                        _ => return Ok(None),
+                    };
+                    let Some(value) = value else {
+                        return Err(syn::Error::new(binary.span(), "arithmetic overflow in discriminant"));
+                    };
+                    self.check_range(value, ty, binary.span())?;
+                    value
+                }
+            }
+            Expr::Cast(cast) => {
+                let Some(target) = IntType::from_type(&cast.ty) else {
+                    return Ok(None);
+                };
+                if let Some(ty) = ty {
+                    self.check_type(target, Some(ty), cast.span())?;
+                }
+                let Some(value) = self.fold(&cast.expr, self.operand_type(&cast.expr))? else {
+                    return Ok(None);
+                };
+                match target.wrap(value) {
+                    Some(value) => value,
+                    None => return Ok(None),
+                }
+            }
+            Expr::Path(path) => match self.variant_reference(path)? {
+                Some(Discriminant::Value(value)) => *value,
+                _ => return Ok(None),
+            },
+            _ => return Ok(None),
+        };
+        Ok(Some(value))
+    }
+
+    /// Folds an integer literal, which is negated as a whole if `negate` is set.
+    ///
+    /// Values of `u128` above `i128::MAX` don't fit the 128-bit signed values the macro
+    /// tracks, so they are left for the compiler to evaluate.
+    fn fold_int_lit(&self, lit_int: &LitInt, ty: Option<IntType>, negate: bool) -> Result<Option<i128>, syn::Error> {
+        if !lit_int.suffix().is_empty() {
+            let Some(suffix) = IntType::from_name(lit_int.suffix()) else {
+                return Err(syn::Error::new(lit_int.span(), "discriminant must be an integer"));
+            };
+            self.check_type(suffix, ty, lit_int.span())?;
+        }
+        let magnitude = lit_int.base10_parse::<u128>()?;
+        let value = if negate { 0i128.checked_sub_unsigned(magnitude) } else { i128::try_from(magnitude).ok() };
+        let Some(value) = value else {
+            return match ty {
+                None | Some(IntType::U128) if !negate => Ok(None),
+                _ => {
+                    let sign = if negate { "-" } else { "" };
+                    let message = match ty {
+                        Some(ty) => format!("discriminant `{sign}{magnitude}` is out of range for `{ty}`"),
+                        None => format!("discriminant `{sign}{magnitude}` is out of range"),
+                    };
+                    Err(syn::Error::new(lit_int.span(), message))
+                }
+            };
+        };
+        self.check_range(value, ty, lit_int.span())?;
+        Ok(Some(value))
+    }
+
+    /// The type of an operand whose type is not determined by its context, e.g. the
+    /// expression being cast.
+    ///
+    /// Like the compiler, this falls back to `i32` for unsuffixed literals.
+    fn operand_type(&self, expr: &Expr) -> Option<IntType> {
+        // Without a known repr, nothing is range checked.
+        self.repr?;
+        Some(self.natural_type(expr).unwrap_or(IntType::I32))
+    }
+
+    fn natural_type(&self, expr: &Expr) -> Option<IntType> {
+        match expr {
+            Expr::Lit(ExprLit { lit: Lit::Int(lit_int), .. }) => IntType::from_name(lit_int.suffix()),
+            Expr::Lit(ExprLit { lit: Lit::Byte(_), .. }) => Some(IntType::U8),
+            Expr::Paren(paren) => self.natural_type(&paren.expr),
+            Expr::Group(group) => self.natural_type(&group.expr),
+            Expr::Unary(unary) => self.natural_type(&unary.expr),
+            Expr::Binary(binary) if matches!(binary.op, BinOp::Shl(_) | BinOp::Shr(_)) => {
+                self.natural_type(&binary.left)
+            }
+            Expr::Binary(binary) => self.natural_type(&binary.left).or(self.natural_type(&binary.right)),
+            Expr::Cast(cast) => IntType::from_type(&cast.ty),
+            Expr::Path(path) if matches!(self.variant_reference(path), Ok(Some(_))) => self.repr,
+            _ => None,
+        }
+    }
+
+    fn check_range(&self, value: i128, ty: Option<IntType>, span: Span) -> Result<(), syn::Error> {
+        match ty {
+            Some(ty) if !ty.contains(value) => Err(syn::Error::new(
+                span,
+                format!("discriminant `{value}` is out of range for `{ty}`"),
+            )),
+            _ => Ok(()),
+        }
+    }
+
+    fn check_type(&self, found: IntType, expected: Option<IntType>, span: Span) -> Result<(), syn::Error> {
+        match expected {
+            Some(expected) if expected != found => Err(syn::Error::new(
+                span,
+                format!("mismatched types: expected `{expected}`, found `{found}`"),
+            )),
+            _ => Ok(()),
+        }
+    }
+
+    /// Resolves `Self::Variant` and `Enum::Variant` to the discriminant of that variant.
+    fn variant_reference(&self, path: &ExprPath) -> Result<Option<&Discriminant>, syn::Error> {
+        if path.qself.is_some() || path.path.segments.len() != 2 {
+            return Ok(None);
+        }
+        let enum_segment = &path.path.segments[0];
+        let variant_segment = &path.path.segments[1];
+        if enum_segment.ident != "Self" && &enum_segment.ident != self.enum_ident {
+            return Ok(None);
+        }
+        self.discriminants.iter()
+            .find(|(ident, _)| ident == &variant_segment.ident)
+            .map(|(_, discriminant)| Some(discriminant))
+            .ok_or(syn::Error::new(
+                path.span(),
+                format!("`{}` must refer to a variant declared before this one", variant_segment.ident),
+            ))
+    }
+}
+
+/// The condition under which an item with these attributes is compiled, as a cfg predicate.
+///
+/// Returns `None` if the item is compiled unconditionally.
+pub(crate) fn cfg_predicate(attrs: &[Attribute]) -> Result<Option<TokenStream>, syn::Error> {
+    let mut predicates = vec![];
+    for attr in attrs {
+        if attr.path().is_ident("cfg") {
+            predicates.push(attr.parse_args::<TokenStream>()?);
+        } else if attr.path().is_ident("cfg_attr") {
+            let mut items = attr.parse_args_with(Punctuated::<Meta, Token![,]>::parse_terminated)?.into_iter();
+            let Some(condition) = items.next() else {
+                continue;
+            };
+            for cfg in items.filter(|item| item.path().is_ident("cfg")) {
+                let predicate = cfg.require_list()?.tokens.clone();
+                predicates.push(quote! { any(not(#condition), #predicate) });
+            }
+        }
+    }
+    Ok((!predicates.is_empty()).then(|| quote! { all(#(#predicates),*) }))
+}
+
+/// Replaces references to other variants with their discriminant, since neither the newtype
+/// nor the rustified enum can evaluate `Self::Variant` the way the original enum would.
+struct VariantSubstitution<'a> {
+    evaluator: &'a DiscriminantEvaluator<'a>,
+    error: Option<syn::Error>,
+}
+
+impl VisitMut for VariantSubstitution<'_> {
+    fn visit_expr_mut(&mut self, expr: &mut Expr) {
+        if let Expr::Path(path) = expr {
+            match self.evaluator.variant_reference(path) {
+                Ok(Some(discriminant)) => *expr = parse_quote! { (#discriminant) },
+                Ok(None) => {}
+                Err(err) => self.error = Some(err),
+            }
+            return;
+        }
+        syn::visit_mut::visit_expr_mut(self, expr);
+    }
+}
@@ -0,0 +1,144 @@
+//! Bit operations for enums representing C flags.
+
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use crate::FfiEnum;
+
+/// Generates the bit operations, flag queries and the flag iterator of a flags enum.
+pub(crate) fn flags_impl(ffi_enum: &FfiEnum) -> TokenStream {
+    let FfiEnum { original_ident, rust_enum_ident, vis, repr_tokens, variants, .. } = ffi_enum;
+    let iter_ident = format_ident!("{}Iter", original_ident);
+    let iter_doc = format!(
+        "An iterator over the single-bit flags of a [`{original_ident}`] value, created by [`{original_ident}::iter`]."
+    );
+    let variant_idents: Vec<_> = variants.iter().map(|variant| &variant.ident).collect();
+    let variant_cfgs: Vec<_> = variants.iter().map(|variant| &variant.cfg).collect();
+
+    let known_mask = quote! {
+        const {
+            let mask = 0;
+            #(
+                #variant_cfgs
+                let mask = mask | #original_ident::#variant_idents.0;
+            )*
+            mask
+        }
+    };
+    // Flags enums often have a constant `ALL` of their own.
+    let known_mask_item = (!ffi_enum.has_constant("KNOWN_MASK")).then(|| quote! {
+        /// The union of all known flags, as a raw value.
+        #vis const KNOWN_MASK: #repr_tokens = #known_mask;
+    });
+    let all_item = (!ffi_enum.has_constant("ALL")).then(|| quote! {
+        /// All known flags.
+        #vis const ALL: Self = Self(#known_mask);
+    });
+
+    let bit_ops = [
+        (quote! { BitOr }, quote! { bitor }, quote! { BitOrAssign }, quote! { bitor_assign }, quote! { | }),
+        (quote! { BitAnd }, quote! { bitand }, quote! { BitAndAssign }, quote! { bitand_assign }, quote! { & }),
+        (quote! { BitXor }, quote! { bitxor }, quote! { BitXorAssign }, quote! { bitxor_assign }, quote! { ^ }),
+    ].into_iter().map(|(op_trait, op_fn, assign_trait, assign_fn, op)| quote! {
+        impl core::ops::#op_trait for #original_ident {
+            type Output = Self;
+
+            fn #op_fn(self, rhs: Self) -> Self {
+                Self(self.0 #op rhs.0)
+            }
+        }
+
+        impl core::ops::#assign_trait for #original_ident {
+            fn #assign_fn(&mut self, rhs: Self) {
+                self.0 = self.0 #op rhs.0;
+            }
+        }
+    });
+
+    quote! {
+        #[allow(deprecated)]
+        impl #original_ident {
+            #known_mask_item
+            #all_item
+
+            /// Returns `true` if all flags set in `other` are also set in `self`.
+            #vis const fn contains(self, other: Self) -> bool {
+                self.0 & other.0 == other.0
+            }
+
+            /// Sets the flags of `other` in `self`.
+            #vis fn insert(&mut self, other: Self) {
+                self.0 |= other.0;
+            }
+
+            /// Clears the flags of `other` in `self`.
+            #vis fn remove(&mut self, other: Self) {
+                self.0 &= !other.0;
+            }
+
+            /// Returns `true` if no flags are set.
+            #vis const fn is_empty(self) -> bool {
+                self.0 == 0
+            }
+
+            /// The bits of `self` which don't belong to any known flag.
+            #vis const fn unknown_bits(self) -> #repr_tokens {
+                self.0 & !#known_mask
+            }
+
+            /// Iterates over the known single-bit flags set in `self`.
+            ///
+            /// Bits without a known flag are skipped, and can be queried with [`Self::unknown_bits`].
+            #vis fn iter(self) -> #iter_ident {
+                #iter_ident { bits: self.0, index: 0 }
+            }
+        }
+
+        #(#bit_ops)*
+
+        impl core::ops::Not for #original_ident {
+            type Output = Self;
+
+            /// Returns the complement of the known flags, clearing all unknown bits.
+            fn not(self) -> Self {
+                Self(!self.0 & #known_mask)
+            }
+        }
+
+        #[doc = #iter_doc]
+        #[derive(Debug, Clone)]
+        #vis struct #iter_ident {
+            bits: #repr_tokens,
+            index: usize,
+        }
+
+        #[allow(deprecated)]
+        impl #iter_ident {
+            const FLAGS: &'static [#repr_tokens] = &[#( #variant_cfgs #original_ident::#variant_idents.0 ),*];
+        }
+
+        impl Iterator for #iter_ident {
+            type Item = #rust_enum_ident;
+
+            fn next(&mut self) -> Option<Self::Item> {
+                while let Some(flag) = Self::FLAGS.get(self.index) {
+                    self.index += 1;
+                    if flag.count_ones() == 1 && self.bits & flag == *flag {
+                        if let Some(flag) = #original_ident(*flag).try_to_rust() {
+                            return Some(flag);
+                        }
+                    }
+                }
+                None
+            }
+        }
+
+        impl IntoIterator for #original_ident {
+            type Item = #rust_enum_ident;
+            type IntoIter = #iter_ident;
+
+            fn into_iter(self) -> Self::IntoIter {
+                self.iter()
+            }
+        }
+    }
+}
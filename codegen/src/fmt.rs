@@ -0,0 +1,176 @@
+//! Formatting impls of the generated enums.
+
+use darling::FromMeta;
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::ext::IdentExt;
+use crate::{CatchAll, FfiEnum, FfiVariant};
+
+/// The names `Display` uses for the variants.
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum DisplayStyle {
+    /// The name of the variant in the rustified enum
+    RustName,
+    /// The name of the associated constant of the FFI enum, which matches the C enumerator
+    CName,
+}
+
+impl FromMeta for DisplayStyle {
+    fn from_word() -> darling::Result<Self> {
+        Ok(DisplayStyle::RustName)
+    }
+
+    fn from_string(value: &str) -> darling::Result<Self> {
+        match value {
+            "rust_name" => Ok(DisplayStyle::RustName),
+            "c_name" => Ok(DisplayStyle::CName),
+            _ => Err(darling::Error::unknown_value(value)),
+        }
+    }
+}
+
+impl DisplayStyle {
+    fn name(self, variant: &FfiVariant) -> String {
+        if let Some(display) = &variant.display {
+            return display.clone();
+        }
+        match self {
+            DisplayStyle::RustName => variant.rust_ident.unraw().to_string(),
+            DisplayStyle::CName => variant.ident.unraw().to_string(),
+        }
+    }
+}
+
+/// Generates a `Debug` impl for the FFI enum, which prints the name of known values.
+///
+/// Unknown values are printed as `Foo(17 /* unknown */)`. For flags, values without a name
+/// of their own are printed as the union of their single-bit flags.
+pub(crate) fn debug_impl(ffi_enum: &FfiEnum, flags: bool) -> TokenStream {
+    let FfiEnum { original_ident, variants, .. } = ffi_enum;
+    let variant_idents: Vec<_> = variants.iter().map(|variant| &variant.ident).collect();
+    let variant_cfgs: Vec<_> = variants.iter().map(|variant| &variant.cfg).collect();
+    let variant_names: Vec<_> = variant_idents.iter()
+        .map(|ident| format!("{original_ident}::{ident}"))
+        .collect();
+    let raw_self = ffi_enum.raw_value(quote! { self });
+    let raw_variants: Vec<_> = variant_idents.iter()
+        .map(|ident| ffi_enum.raw_value(quote! { #original_ident::#ident }))
+        .collect();
+    // `also` values are printed as the variant they convert to.
+    let also_values = ffi_enum.also_values();
+    let also_cfgs = also_values.iter().map(|(variant, _)| &variant.cfg);
+    let also_names = also_values.iter().map(|(variant, _)| format!("{original_ident}::{}", variant.ident));
+    let also_values = also_values.iter().map(|(_, value)| value);
+    let unknown_format = format!("{original_ident}({{:?}} /* unknown */)");
+    let fallback = if flags {
+        let unknown_bits_format = format!("{{}}{original_ident}({{:#x}} /* unknown */)");
+        quote! {
+            const FLAGS: &[(#original_ident, &str)] = &[#( #variant_cfgs (#original_ident::#variant_idents, #variant_names) ),*];
+            let mut remaining = self.0;
+            let mut separator = "";
+            for (flag, name) in FLAGS {
+                if flag.0.count_ones() == 1 && remaining & flag.0 == flag.0 {
+                    write!(f, "{}{}", separator, name)?;
+                    remaining &= !flag.0;
+                    separator = " | ";
+                }
+            }
+            if remaining != 0 || separator.is_empty() {
+                write!(f, #unknown_bits_format, separator, remaining)?;
+            }
+            Ok(())
+        }
+    } else {
+        quote! { write!(f, #unknown_format, self.0) }
+    };
+    quote! {
+        #[allow(deprecated)]
+        impl core::fmt::Debug for #original_ident {
+            fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                match #raw_self {
+                    #(
+                        #variant_cfgs
+                        raw if raw == #raw_variants => f.write_str(#variant_names),
+                    )*
+                    #( #also_cfgs #also_values => f.write_str(#also_names), )*
+                    _ => {
+                        #fallback
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Generates `Display` impls for the FFI enum and the rustified enum.
+///
+/// Values without a name are displayed as their raw value.
+pub(crate) fn display_impls(ffi_enum: &FfiEnum, style: DisplayStyle) -> TokenStream {
+    let FfiEnum { original_ident, rust_enum_ident, variants, .. } = ffi_enum;
+    let variant_idents: Vec<_> = variants.iter().map(|variant| &variant.ident).collect();
+    let rust_idents: Vec<_> = variants.iter().map(|variant| &variant.rust_ident).collect();
+    let variant_cfgs: Vec<_> = variants.iter().map(|variant| &variant.cfg).collect();
+    let variant_names: Vec<_> = variants.iter().map(|variant| style.name(variant)).collect();
+    let raw_self = ffi_enum.raw_value(quote! { self });
+    let raw_variants: Vec<_> = variant_idents.iter()
+        .map(|ident| ffi_enum.raw_value(quote! { #original_ident::#ident }))
+        .collect();
+    let also_values = ffi_enum.also_values();
+    let also_cfgs = also_values.iter().map(|(variant, _)| &variant.cfg);
+    let also_names = also_values.iter().map(|(variant, _)| style.name(variant));
+    let also_values = also_values.iter().map(|(_, value)| value);
+    let skipped_cfgs = ffi_enum.skipped.iter().map(|variant| &variant.cfg);
+    let skipped_idents = ffi_enum.skipped.iter().map(|variant| &variant.rust_ident);
+    let skipped_names = ffi_enum.skipped.iter().map(|variant| style.name(variant));
+    let catch_all_arm = match ffi_enum.extra_catch_all() {
+        Some(CatchAll { ident, keep_value: true, .. }) => quote! {
+            #rust_enum_ident::#ident(raw) => core::fmt::Display::fmt(raw, f),
+        },
+        Some(CatchAll { ident, .. }) => {
+            let name = ident.to_string();
+            quote! { #rust_enum_ident::#ident => f.write_str(#name), }
+        }
+        None => quote! {},
+    };
+    quote! {
+        #[allow(deprecated)]
+        impl core::fmt::Display for #original_ident {
+            fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                match #raw_self {
+                    #(
+                        #variant_cfgs
+                        raw if raw == #raw_variants => f.write_str(#variant_names),
+                    )*
+                    #( #also_cfgs #also_values => f.write_str(#also_names), )*
+                    raw => core::fmt::Display::fmt(&raw, f),
+                }
+            }
+        }
+
+        #[allow(deprecated)]
+        impl core::fmt::Display for #rust_enum_ident {
+            fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                match self {
+                    #( #variant_cfgs #rust_enum_ident::#rust_idents => f.write_str(#variant_names), )*
+                    #( #skipped_cfgs #rust_enum_ident::#skipped_idents => f.write_str(#skipped_names), )*
+                    #catch_all_arm
+                }
+            }
+        }
+    }
+}
+
+/// Forwards the integer formatting traits to the raw value of the FFI enum.
+pub(crate) fn radix_impls(ffi_enum: &FfiEnum) -> TokenStream {
+    let original_ident = &ffi_enum.original_ident;
+    let traits = [quote! { Binary }, quote! { Octal }, quote! { LowerHex }, quote! { UpperHex }];
+    quote! {
+        #(
+            impl core::fmt::#traits for #original_ident {
+                fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                    core::fmt::#traits::fmt(&self.0, f)
+                }
+            }
+        )*
+    }
+}
@@ -0,0 +1,303 @@
+//! C and C++ header fragments of the FFI enums, for C code which doesn't use cbindgen.
+
+use std::fmt::Write;
+use std::path::Path;
+use std::{fmt, fs};
+use darling::ast::NestedMeta;
+use darling::FromMeta;
+use syn::ext::IdentExt;
+use syn::spanned::Spanned;
+use syn::{Attribute, Item, ItemEnum, Meta};
+use crate::discriminant::{Discriminant, DiscriminantEvaluator};
+use crate::module::{is_c_style, merge_args, take_enum_args, Selection};
+use crate::repr::{enum_reprs, EnumRepr, IntType};
+use crate::{doc_string, ffi_variant, newtype_repr, Error, MacroArgs};
+
+/// Generates a C header fragment for the enums annotated with `#[enum_ffi]` in Rust source
+/// files, including the enums converted by `#[enum_ffi_module]`.
+///
+/// Every enum becomes a `typedef` of its integer type with a `#define` for every variant,
+/// named like the constants of the `cbindgen` option. The Rust source stays the single source
+/// of truth, e.g. when the header is regenerated by a build script or a test.
+///
+/// ```no_run
+/// # fn main() -> Result<(), enum_ffi_newtype_codegen::Error> {
+/// enum_ffi_newtype_codegen::Header::new()
+///     .cpp_enum_class(true)
+///     .source_file("src/lib.rs")?
+///     .write_file("include/enums.h")?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Header {
+    enums: Vec<CEnum>,
+    cpp_enum_class: bool,
+}
+
+/// An FFI enum, as seen from C.
+#[derive(Debug, Clone)]
+struct CEnum {
+    name: String,
+    doc: String,
+    int_type: IntType,
+    non_zero: bool,
+    variants: Vec<CVariant>,
+}
+
+#[derive(Debug, Clone)]
+struct CVariant {
+    /// The name of the `#define`
+    constant: String,
+    /// The name of the enumerator of the C++ `enum class`
+    enumerator: String,
+    value: i128,
+    doc: String,
+}
+
+impl Header {
+    /// A header without any enums.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Declares every enum as a C++ `enum class` with the same underlying type when the header
+    /// is included from C++, instead of the `typedef`.
+    pub fn cpp_enum_class(mut self, cpp_enum_class: bool) -> Self {
+        self.cpp_enum_class = cpp_enum_class;
+        self
+    }
+
+    /// Adds the FFI enums of the Rust source code `source`, in declaration order.
+    pub fn source_str(mut self, source: &str) -> Result<Self, Error> {
+        let file = syn::parse_file(source)?;
+        collect_enums(&file.items, None, &mut self.enums)?;
+        Ok(self)
+    }
+
+    /// Adds the FFI enums of the Rust file at `path`, in declaration order.
+    ///
+    /// Modules in other files are not followed, they have to be added separately.
+    pub fn source_file(self, path: impl AsRef<Path>) -> Result<Self, Error> {
+        let source = fs::read_to_string(path)?;
+        self.source_str(&source)
+    }
+
+    /// Writes the header to the file at `path`.
+    pub fn write_file(&self, path: impl AsRef<Path>) -> Result<(), Error> {
+        fs::write(path, self.to_string())?;
+        Ok(())
+    }
+}
+
+impl fmt::Display for Header {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "/* Generated by enum-ffi-newtype from the Rust definitions, do not edit. */")?;
+        writeln!(f)?;
+        writeln!(f, "#include <stdint.h>")?;
+        for c_enum in &self.enums {
+            writeln!(f)?;
+            if self.cpp_enum_class {
+                writeln!(f, "#ifdef __cplusplus")?;
+                c_enum.write_cpp(f)?;
+                writeln!(f, "#else")?;
+                c_enum.write_c(f)?;
+                writeln!(f, "#endif")?;
+            } else {
+                c_enum.write_c(f)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl CEnum {
+    fn doc(&self) -> String {
+        let mut doc = self.doc.clone();
+        if self.non_zero {
+            if !doc.is_empty() {
+                doc.push_str("\n\n");
+            }
+            doc.push_str("Never 0, so that 0 can stand for the absence of a value, e.g. for success.");
+        }
+        doc
+    }
+
+    /// Writes the `typedef` and the `#define`s.
+    ///
+    /// `#define`s are used rather than an `enum`, whose enumerators are `int`s before C23.
+    fn write_c(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_doc(f, "", &self.doc())?;
+        writeln!(f, "typedef {} {};", c_type(self.int_type), self.name)?;
+        for variant in &self.variants {
+            write_doc(f, "", &variant.doc)?;
+            writeln!(f, "#define {} (({}){})", variant.constant, self.name, c_literal(variant.value))?;
+        }
+        Ok(())
+    }
+
+    fn write_cpp(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_doc(f, "", &self.doc())?;
+        writeln!(f, "enum class {} : {} {{", self.name, c_type(self.int_type))?;
+        for variant in &self.variants {
+            write_doc(f, "  ", &variant.doc)?;
+            writeln!(f, "  {} = {},", variant.enumerator, c_literal(variant.value))?;
+        }
+        writeln!(f, "}};")
+    }
+}
+
+/// Writes `doc` as a `/** ... */` comment, if it isn't empty.
+fn write_doc(f: &mut fmt::Formatter<'_>, indent: &str, doc: &str) -> fmt::Result {
+    if doc.is_empty() {
+        return Ok(());
+    }
+    writeln!(f, "{indent}/**")?;
+    for line in doc.lines() {
+        let mut comment = format!("{indent} *");
+        if !line.is_empty() {
+            write!(comment, " {}", line.replace("*/", "* /"))?;
+        }
+        writeln!(f, "{comment}")?;
+    }
+    writeln!(f, "{indent} */")
+}
+
+/// The `<stdint.h>` type of `int_type`.
+fn c_type(int_type: IntType) -> &'static str {
+    match int_type {
+        IntType::U8 => "uint8_t",
+        IntType::U16 => "uint16_t",
+        IntType::U32 => "uint32_t",
+        IntType::U64 => "uint64_t",
+        IntType::Usize => "uintptr_t",
+        IntType::I8 => "int8_t",
+        IntType::I16 => "int16_t",
+        IntType::I32 => "int32_t",
+        IntType::I64 => "int64_t",
+        IntType::Isize => "intptr_t",
+        IntType::U128 | IntType::I128 => unreachable!("128-bit enums are rejected"),
+    }
+}
+
+/// A C integer literal of `value`, which is valid for all values of 64-bit types.
+fn c_literal(value: i128) -> String {
+    if value > i128::from(i64::MAX) {
+        format!("{value}ULL")
+    } else if value == i128::from(i64::MIN) {
+        // The literal `9223372036854775808` has no signed type.
+        format!("({}LL - 1)", value + 1)
+    } else if i32::try_from(value).is_err() {
+        format!("{value}LL")
+    } else {
+        value.to_string()
+    }
+}
+
+/// Whether `attr` is the attribute `name`, possibly given by its path.
+fn is_attr(attr: &Attribute, name: &str) -> bool {
+    attr.path().segments.last().is_some_and(|segment| segment.ident == name)
+}
+
+/// The selection and default arguments of an `#[enum_ffi_module(...)]` module.
+type ModuleArgs = (Selection, Vec<NestedMeta>);
+
+/// Collects the FFI enums in `items`, where `module` is the innermost `#[enum_ffi_module]`
+/// around them.
+fn collect_enums(items: &[Item], module: Option<&ModuleArgs>, enums: &mut Vec<CEnum>) -> Result<(), syn::Error> {
+    for item in items {
+        match item {
+            Item::Enum(item_enum) => {
+                let annotated = item_enum.attrs.iter().any(|attr| is_attr(attr, "enum_ffi"));
+                let args = match module {
+                    Some((selection, defaults)) if selection.includes(item_enum) && is_c_style(item_enum) => {
+                        merge_args(defaults, take_enum_args(&mut item_enum.clone())?)
+                    }
+                    _ if annotated => take_enum_args(&mut item_enum.clone())?,
+                    _ => continue,
+                };
+                enums.push(c_enum(item_enum, &args)?);
+            }
+            Item::Mod(item_mod) => {
+                let Some((_, items)) = &item_mod.content else {
+                    continue;
+                };
+                match item_mod.attrs.iter().find(|attr| is_attr(attr, "enum_ffi_module")) {
+                    Some(attr) => {
+                        let mut args = match &attr.meta {
+                            Meta::List(list) => NestedMeta::parse_meta_list(list.tokens.clone())?,
+                            _ => vec![],
+                        };
+                        let selection = Selection::from_args(&mut args)?;
+                        collect_enums(items, Some(&(selection, args)), enums)?;
+                    }
+                    None => collect_enums(items, module, enums)?,
+                }
+            }
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+/// The C view of `item_enum`, converted by `enum_ffi` with the arguments `args`.
+fn c_enum(item_enum: &ItemEnum, args: &[NestedMeta]) -> Result<CEnum, syn::Error> {
+    let mut macro_args = MacroArgs::from_list(args)?;
+    macro_args.resolve(&item_enum.ident);
+    let int_type = match enum_reprs(&item_enum.attrs)?.as_slice() {
+        [EnumRepr { cfg: None, kind }] => newtype_repr(item_enum, *kind)?.0,
+        [] => return Err(syn::Error::new(item_enum.span(), "No `repr` attribute found.")),
+        _ => return Err(syn::Error::new(
+            item_enum.ident.span(),
+            "the `repr` of the enum differs between targets, so it can't be written to a header",
+        )),
+    };
+    if int_type.bits() == Some(128) {
+        return Err(syn::Error::new(item_enum.ident.span(), "128-bit integers have no C type"));
+    }
+    let names = macro_args.cbindgen.take().unwrap_or_default();
+    let mut discriminants = DiscriminantEvaluator::new(&item_enum.ident, Some(int_type));
+    let mut variants = vec![];
+    for variant in &item_enum.variants {
+        let ffi_variant = ffi_variant(variant, &mut discriminants, &macro_args)?;
+        if ffi_variant.skip.is_some() {
+            continue;
+        }
+        let Discriminant::Value(value) = ffi_variant.discriminant else {
+            return Err(syn::Error::new(
+                variant.span(),
+                "the value of the variant can't be evaluated by the macro, so it can't be written to a header",
+            ));
+        };
+        let mut doc = ffi_variant.doc;
+        for attr in variant.attrs.iter().filter(|attr| attr.path().is_ident("cfg")) {
+            if !doc.is_empty() {
+                doc.push_str("\n\n");
+            }
+            let predicate = attr.meta.require_list()?.tokens.to_string();
+            write!(doc, "Only available with `cfg({predicate})`.").unwrap();
+        }
+        let constant = names.constant_name(&item_enum.ident, &ffi_variant.ident).to_string();
+        let aliases: Vec<_> = ffi_variant.aliases.iter().map(|alias| CVariant {
+            constant: names.constant_name(&item_enum.ident, alias).to_string(),
+            enumerator: alias.unraw().to_string(),
+            value,
+            doc: format!("Alias of `{constant}`."),
+        }).collect();
+        variants.push(CVariant {
+            constant,
+            enumerator: ffi_variant.ident.unraw().to_string(),
+            value,
+            doc,
+        });
+        variants.extend(aliases);
+    }
+    Ok(CEnum {
+        name: item_enum.ident.unraw().to_string(),
+        doc: doc_string(&item_enum.attrs),
+        int_type,
+        // The C type of a `status` enum includes the success value.
+        non_zero: macro_args.non_zero && !macro_args.status,
+        variants,
+    })
+}
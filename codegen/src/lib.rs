@@ -0,0 +1,1183 @@
+//! The code generator of `enum-ffi-newtype`
+//!
+//! The `#[enum_ffi]`, `#[enum_ffi_module]` and `#[enum_ffi_rustify]` attributes are thin
+//! wrappers around [`enum_ffi`], [`enum_ffi_module`] and [`enum_ffi_rustify`]. Build scripts can use [`Rewriter`] to apply the same
+//! transformation to generated bindings, which can't be annotated by hand, and [`Header`] to
+//! generate a C header of the FFI enums.
+
+mod cbindgen;
+mod convert;
+mod discriminant;
+mod flags;
+mod fmt;
+mod header;
+mod module;
+mod names;
+mod raw;
+mod rename;
+mod repr;
+mod reverse;
+mod rewrite;
+mod serde;
+mod status;
+mod variants;
+
+use std::{iter, mem};
+use darling::ast::NestedMeta;
+use darling::util::PathList;
+use darling::{FromMeta, FromVariant};
+use proc_macro2::TokenStream;
+
+
+use syn::punctuated::Punctuated;
+use syn::{parse_quote, Attribute, Fields, Ident, Item, ItemEnum, Meta, Path, Token, Variant, Visibility};
+use quote::{format_ident, quote, ToTokens};
+use syn::ext::IdentExt;
+use syn::spanned::Spanned;
+use crate::cbindgen::CbindgenArgs;
+use crate::discriminant::{cfg_predicate, Discriminant, DiscriminantEvaluator};
+use crate::fmt::DisplayStyle;
+use crate::names::NameOptions;
+use crate::rename::{renamed_ident, RenameRule};
+use crate::repr::{enum_reprs, strip_reprs, EnumRepr, IntType, ReprKind};
+use crate::serde::SerdeEncoding;
+
+pub use crate::header::Header;
+pub use crate::rewrite::{Error, Rewriter};
+
+/// Expands `#[enum_ffi(args)]` on the enum `item`.
+pub fn enum_ffi(args: TokenStream, item: TokenStream) -> Result<TokenStream, syn::Error> {
+    let item_enum = syn::parse2(item)?;
+    let attr_args = NestedMeta::parse_meta_list(args)?;
+    enum_ffi_newtype(item_enum, &attr_args)
+}
+
+/// Expands `#[enum_ffi_module(args)]` on the module `item`.
+pub fn enum_ffi_module(args: TokenStream, item: TokenStream) -> Result<TokenStream, syn::Error> {
+    let item_mod = syn::parse2(item)?;
+    let attr_args = NestedMeta::parse_meta_list(args)?;
+    module::enum_ffi_module(item_mod, attr_args)
+}
+
+/// Expands `#[enum_ffi_rustify(args)]` on the `impl` block or module `item`.
+pub fn enum_ffi_rustify(args: TokenStream, item: TokenStream) -> Result<TokenStream, syn::Error> {
+    let item = syn::parse2(item)?;
+    let attr_args = NestedMeta::parse_meta_list(args)?;
+    reverse::enum_ffi_rustify(item, attr_args)
+}
+
+/// Expands `ffi_try!(Status, raw)`, which returns early if the raw status `raw` is an error of
+/// the `status` enum `Status`.
+pub fn ffi_try(input: TokenStream) -> Result<TokenStream, syn::Error> {
+    status::ffi_try(input)
+}
+
+/// The integer type of the FFI newtype of `item_enum` with the representation `kind`, and the
+/// type of its field.
+///
+/// `repr(C)` is a C `int`, or an `unsigned int` if the discriminants don't fit into an `int`,
+/// like C compilers choose the type of an `enum`.
+fn newtype_repr(item_enum: &ItemEnum, kind: ReprKind) -> Result<(IntType, TokenStream), syn::Error> {
+    match kind {
+        ReprKind::Int(int_type) => {
+            let ident = format_ident!("{}", int_type.to_string());
+            Ok((int_type, quote! { #ident }))
+        }
+        ReprKind::C => {
+            let mut discriminants = DiscriminantEvaluator::new(&item_enum.ident, None);
+            let mut unsigned = false;
+            for variant in &item_enum.variants {
+                if let Discriminant::Value(value) = discriminants.push_variant(variant)? {
+                    unsigned |= *value > i128::from(i32::MAX);
+                }
+            }
+            if unsigned {
+                Ok((IntType::U32, quote! { core::ffi::c_uint }))
+            } else {
+                Ok((IntType::I32, quote! { core::ffi::c_int }))
+            }
+        }
+    }
+}
+
+/// Adds `#[cfg(predicate)]` to every item in `tokens`.
+fn cfg_gated(tokens: TokenStream, predicate: &TokenStream) -> Result<TokenStream, syn::Error> {
+    let mut file: syn::File = syn::parse2(tokens)?;
+    for item in &mut file.items {
+        let attrs = match item {
+            Item::Const(item) => &mut item.attrs,
+            Item::Enum(item) => &mut item.attrs,
+            Item::Fn(item) => &mut item.attrs,
+            Item::Impl(item) => &mut item.attrs,
+            Item::Macro(item) => &mut item.attrs,
+            Item::Mod(item) => &mut item.attrs,
+            Item::Static(item) => &mut item.attrs,
+            Item::Struct(item) => &mut item.attrs,
+            Item::Trait(item) => &mut item.attrs,
+            Item::Type(item) => &mut item.attrs,
+            Item::Use(item) => &mut item.attrs,
+            item => return Err(syn::Error::new(item.span(), "unexpected item in the expansion of `enum_ffi`")),
+        };
+        attrs.insert(0, parse_quote! { #[cfg(#predicate)] });
+    }
+    Ok(file.into_token_stream())
+}
+
+#[derive(Debug, FromMeta)]
+struct MacroArgs {
+    /// Let the FFI enum be represented by a NonZero type
+    ///
+    /// This is mainly useful for C-Result enums, where 0 is the success case.
+    /// In this case we want our Rust enum to not contain the success variant,
+    /// and instead have a NonZero error enum. The type used in the FFI can
+    /// then be `Result<(), NonZeroFfiEnum>`. With `status`, the success variant can stay in
+    /// the enum.
+    #[darling(default)]
+    non_zero: bool,
+    /// Convert a C status enum, whose success variant has the value 0, to an error enum
+    ///
+    /// Implies `non_zero`. The success variant only remains as the raw value 0, so the FFI enum
+    /// and the rustified enum represent errors. `Foo::into_result` converts a raw status to
+    /// `Result<(), Foo>`, and `Foo::from_result` converts it back. The `ffi_try!` macro returns
+    /// the error of a raw status early, like `?`.
+    #[darling(default)]
+    status: bool,
+    /// The success variant of a `status` enum
+    ///
+    /// Defaults to the variant with the value 0.
+    success: Option<String>,
+    /// A fallback catch-all enum variant of the rustified enum
+    ///
+    /// If not specified, this macro will inject a new catch-all variant at
+    /// the end of the enum.
+    /// If the specified enum variant already exists, then no new variant will be created,
+    /// but it is still used as the catch-all fallback.
+    /// `{}` is replaced by the name of the enum.
+    catch_all: Option<String>,
+    /// Let the catch-all variant carry the raw value it was created from
+    ///
+    /// The catch-all variant becomes a tuple variant holding the FFI representation,
+    /// so that converting an unknown value to the rust enum and back is lossless.
+    #[darling(default)]
+    keep_value: bool,
+    /// Don't add a catch-all variant to the rustified enum
+    ///
+    /// Unknown values can then only be converted fallibly, so `TryFrom` is implemented
+    /// instead of `From` for the conversion from the FFI enum to the rustified enum, with a
+    /// `TryFrom{Enum}Error` holding the unknown value.
+    /// Other enums can use the inherent `try_to_rust` method of the FFI enum, which returns
+    /// `None` for unknown values, for a strict conversion.
+    #[darling(default)]
+    exhaustive: bool,
+    /// Treat the enum variants as bit flags, which can be combined
+    ///
+    /// The FFI enum gets bit operators, flag queries and an iterator over the
+    /// single-bit flags it contains.
+    #[darling(default)]
+    flags: bool,
+    /// Generate tables of the known variants
+    ///
+    /// The FFI enum gets `KNOWN`, `VARIANT_COUNT`, `VARIANTS`, `MIN_KNOWN` and `MAX_KNOWN`, and
+    /// the rustified enum gets `ALL`, `VARIANT_COUNT` and `iter`.
+    #[darling(default)]
+    variants: bool,
+    /// The derives of the FFI enum
+    ///
+    /// Defaults to `Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash`.
+    /// `Debug` is not derived, but implemented by the macro to print the variant names.
+    newtype_derive: Option<PathList>,
+    /// Make the field of the FFI enum private, so that only known values and values checked by
+    /// the constructors can be created outside of its module
+    ///
+    /// The raw value is accessed with `from_raw`, `into_raw` and `as_raw` instead, and
+    /// `checked` only accepts values which convert to a variant. `non_zero` enums also get the unsafe
+    /// `from_raw_unchecked`, which takes the integer rather than the `NonZero`.
+    #[darling(default)]
+    private_field: bool,
+    /// Additional attributes for the FFI enum, e.g. `newtype_attr(must_use)`
+    #[darling(default)]
+    newtype_attr: AttrList,
+    /// Copy the derives of the original enum to the FFI enum
+    ///
+    /// They are added to the derives from `newtype_derive`, or replace the default derives.
+    /// A derived `Default` is implemented via the `#[default]` variant of the enum.
+    #[darling(default)]
+    inherit_derives: bool,
+    /// Implement `Display` for the FFI enum and the rustified enum
+    ///
+    /// Variants are displayed by the name of the rustified variant (`display` or
+    /// `display = "rust_name"`) or by the name of the FFI constant (`display = "c_name"`).
+    /// Individual variants can override this with `#[enum_ffi(display = "...")]`.
+    display: Option<DisplayStyle>,
+    /// Generate `name` and `from_name` for the FFI enum and the rustified enum, and `FromStr`
+    ///
+    /// The error of `FromStr` holds the unknown name as a `String`, so `names` needs `std`.
+    /// `serde` and the `name_*` options imply `names`.
+    #[darling(default)]
+    names: bool,
+    /// Let `from_name` and `FromStr` ignore the ASCII case of names
+    #[darling(default)]
+    name_case_insensitive: bool,
+    /// Let `from_name` and `FromStr` also accept C-style names
+    ///
+    /// Besides its own name, a variant can then be looked up by the name of the other enum
+    /// and by the SCREAMING_SNAKE_CASE form of its name, e.g. `FOO_A` for `FooA`.
+    #[darling(default)]
+    name_aliases: bool,
+    /// Implement `Serialize` and `Deserialize` for the FFI enum and the rustified enum
+    ///
+    /// Requires the `serde` feature. Values are encoded by the name of their constant
+    /// (`serde = "name"`), by their raw value (`serde = "number"`), or by name if they
+    /// are known and by raw value otherwise (`serde` or `serde = "name_or_number"`).
+    serde: Option<SerdeEncoding>,
+    /// Shape the output for cbindgen with `parse.expand`, so that the FFI enum is exported as
+    /// a `typedef` of its integer type with a `#define` for every variant
+    ///
+    /// The constants are named `Foo_A` by default. `cbindgen(prefix = "FOO_")` sets the prefix
+    /// of the names, where `{}` is replaced by the name of the enum, and
+    /// `cbindgen(screaming_snake_case)` converts the names to SCREAMING_SNAKE_CASE.
+    cbindgen: Option<CbindgenArgs>,
+    /// Remove this prefix from the names of the rustified variants, e.g. `FOO_STATUS_` to turn
+    /// `FOO_STATUS_NOT_READY` into `NOT_READY`
+    ///
+    /// The associated constants keep the names of the original variants, which match the C
+    /// enumerators, unless they are renamed with the `const_*` options. `{}` is replaced by the
+    /// name of the enum.
+    strip_prefix: Option<String>,
+    /// Convert the names of the rustified variants to `"PascalCase"`, `"camelCase"`,
+    /// `"snake_case"` or `"SCREAMING_SNAKE_CASE"`, after removing `strip_prefix`
+    ///
+    /// Individual variants can override their rustified name with `#[enum_ffi(rename = "...")]`.
+    rename_all: Option<RenameRule>,
+    /// Remove this prefix from the names of the associated constants of the FFI enum, like
+    /// `strip_prefix` does for the rustified variants
+    const_strip_prefix: Option<String>,
+    /// Convert the names of the associated constants of the FFI enum, like `rename_all` does for
+    /// the rustified variants
+    ///
+    /// Individual variants can override the name of their constant with
+    /// `#[enum_ffi(const_rename = "...")]`.
+    const_rename_all: Option<RenameRule>,
+    /// The identifier the safe Rust enum should have
+    ///
+    /// The newtype FFI enum will get the original enum name.
+    /// `{}` is replaced by the name of the enum.
+    rust_enum_name: Option<String>,
+}
+
+/// Options of a single variant, given as `#[enum_ffi(...)]` on the variant
+#[derive(Debug, FromVariant)]
+#[darling(attributes(enum_ffi))]
+struct VariantArgs {
+    /// The string `Display` uses for this variant
+    display: Option<String>,
+    /// The name of the variant in the rustified enum, instead of the name of the original variant
+    rename: Option<String>,
+    /// The name of the associated constant of the FFI enum, instead of the name of the original
+    /// variant
+    const_rename: Option<String>,
+    /// Other names of the variant, which become additional associated constants with its value
+    #[darling(multiple)]
+    alias: Vec<String>,
+    /// Other raw values which are converted to this variant, e.g. deprecated values of a C API
+    ///
+    /// Values above `i128::MAX` are not supported.
+    #[darling(default)]
+    also: RawValues,
+    /// Only add the variant to the rustified enum, without an associated constant
+    ///
+    /// The variant has no raw value of its own, so no raw value converts to it. With `skip`,
+    /// converting it to the FFI enum fails, which makes the conversion fallible: `try_to_ffi`
+    /// and `TryFrom` replace `to_ffi` and `From`. With `skip = <raw value>` it converts to that
+    /// raw value instead.
+    skip: Option<Skip>,
+}
+
+/// A variant of the original enum, which exists in both the FFI enum and the rustified enum,
+/// unless it is skipped.
+pub(crate) struct FfiVariant {
+    /// The name of the associated constant, which is the name of the original variant unless it
+    /// is renamed
+    pub(crate) ident: Ident,
+    /// The name of the variant in the rustified enum
+    pub(crate) rust_ident: Ident,
+    /// A `#[cfg]` attribute for every item mentioning the variant, if it is compiled conditionally.
+    pub(crate) cfg: TokenStream,
+    /// The string `Display` uses for this variant, instead of its name
+    pub(crate) display: Option<String>,
+    /// The doc comment of the variant
+    pub(crate) doc: String,
+    pub(crate) discriminant: Discriminant,
+    /// Additional associated constants with the value of the variant
+    pub(crate) aliases: Vec<Ident>,
+    /// Additional raw values which are converted to the variant, but never produced by it
+    pub(crate) also: Vec<i128>,
+    /// Whether the variant only exists in the rustified enum, and how it converts to the FFI enum
+    pub(crate) skip: Option<Skip>,
+    /// Whether this is the success variant of a `status` enum, which only exists as a raw value
+    pub(crate) success: bool,
+}
+
+/// The catch-all variant of the rustified enum.
+pub(crate) struct CatchAll {
+    pub(crate) ident: Ident,
+    /// Whether the variant was added by the macro, rather than taken from the original enum
+    pub(crate) injected: bool,
+    /// Whether the variant carries the raw value
+    pub(crate) keep_value: bool,
+}
+
+/// The generated enums, as needed by the generators of the optional impls.
+pub(crate) struct FfiEnum {
+    /// The name of the original enum, which is used for the FFI newtype
+    pub(crate) original_ident: Ident,
+    pub(crate) rust_enum_ident: Ident,
+    pub(crate) vis: Visibility,
+    /// The type of the field of the FFI newtype
+    pub(crate) repr_tokens: TokenStream,
+    /// The integer type of the original enum, which differs from `repr_tokens` for `non_zero`
+    pub(crate) base_repr_tokens: TokenStream,
+    pub(crate) non_zero: bool,
+    pub(crate) variants: Vec<FfiVariant>,
+    /// The variants which only exist in the rustified enum
+    pub(crate) skipped: Vec<FfiVariant>,
+    pub(crate) catch_all: Option<CatchAll>,
+    /// Whether the output is shaped for cbindgen
+    pub(crate) cbindgen: bool,
+}
+
+impl FfiEnum {
+    /// Whether an associated constant of the FFI enum is named `name`, so that a generated
+    /// associated item of that name has to be left out.
+    pub(crate) fn has_constant(&self, name: &str) -> bool {
+        self.variants.iter()
+            .flat_map(|variant| iter::once(&variant.ident).chain(&variant.aliases))
+            .any(|ident| ident == name)
+    }
+
+    /// Whether a variant of the rustified enum is named `name`, which would shadow a generated
+    /// associated item of that name.
+    pub(crate) fn has_rust_variant(&self, name: &str) -> bool {
+        self.variants.iter().chain(&self.skipped).any(|variant| variant.rust_ident == name)
+            || self.catch_all.as_ref().is_some_and(|catch_all| catch_all.ident == name)
+    }
+
+    /// The `also` values of the variants, each with the variant it converts to.
+    pub(crate) fn also_values(&self) -> Vec<(&FfiVariant, Discriminant)> {
+        self.variants.iter()
+            .flat_map(|variant| variant.also.iter().map(move |&value| (variant, Discriminant::Value(value))))
+            .collect()
+    }
+
+    /// The catch-all variant, if it is not one of `variants`.
+    pub(crate) fn extra_catch_all(&self) -> Option<&CatchAll> {
+        self.catch_all.as_ref().filter(|catch_all| catch_all.injected || catch_all.keep_value)
+    }
+
+    /// The attribute hiding an item from cbindgen, if the output is shaped for cbindgen.
+    pub(crate) fn cbindgen_ignore(&self) -> Option<TokenStream> {
+        self.cbindgen.then(cbindgen::ignore)
+    }
+
+    /// A const expression for the FFI enum with the primitive value `raw`.
+    pub(crate) fn ffi_value(&self, raw: TokenStream) -> TokenStream {
+        let original_ident = &self.original_ident;
+        if self.non_zero {
+            quote! { #original_ident(const { core::num::NonZero::new(#raw).unwrap() }) }
+        } else {
+            quote! { #original_ident(#raw) }
+        }
+    }
+
+    /// Whether converting the rustified enum to the FFI enum can fail, due to a skipped
+    /// variant without a raw value.
+    pub(crate) fn fallible_to_ffi(&self) -> bool {
+        self.skipped.iter().any(|variant| matches!(variant.skip, Some(Skip::Fallible)))
+    }
+
+    /// An expression for the primitive value of the FFI enum `value`, which can be used in
+    /// const contexts.
+    pub(crate) fn raw_value(&self, value: TokenStream) -> TokenStream {
+        if self.non_zero {
+            quote! { #value.0.get() }
+        } else {
+            quote! { #value.0 }
+        }
+    }
+}
+
+/// Whether a variant attribute also applies to the associated constant of the FFI enum.
+fn is_forwarded_variant_attr(attr: &Attribute) -> bool {
+    ["doc", "deprecated", "allow", "expect", "cfg", "cfg_attr"].iter().any(|name| attr.path().is_ident(name))
+}
+
+/// The copy of a forwarded variant attribute for the associated constants of the FFI enum.
+///
+/// `expect` becomes `allow`, since the expected lint usually doesn't fire on the constants, and
+/// an unfulfilled expectation is a warning of its own.
+fn forwarded_variant_attr(attr: &Attribute) -> Attribute {
+    fn expect_to_allow(meta: &mut Meta) {
+        if let Meta::List(list) = meta {
+            if list.path.is_ident("expect") {
+                list.path = parse_quote! { allow };
+            }
+        }
+    }
+
+    let mut attr = attr.clone();
+    if let Meta::List(list) = &mut attr.meta {
+        if list.path.is_ident("cfg_attr") {
+            if let Ok(mut metas) = list.parse_args_with(Punctuated::<Meta, Token![,]>::parse_terminated) {
+                // The first item is the predicate.
+                metas.iter_mut().skip(1).for_each(expect_to_allow);
+                list.tokens = metas.into_token_stream();
+            }
+        }
+    }
+    expect_to_allow(&mut attr.meta);
+    attr
+}
+
+/// The text of the doc comments in `attrs`, without the leading space of each line.
+fn doc_string(attrs: &[Attribute]) -> String {
+    let lines: Vec<_> = attrs.iter()
+        .filter_map(|attr| match &attr.meta {
+            Meta::NameValue(name_value) if name_value.path.is_ident("doc") => match &name_value.value {
+                syn::Expr::Lit(syn::ExprLit { lit: syn::Lit::Str(doc), .. }) => Some(doc.value()),
+                _ => None,
+            },
+            _ => None,
+        })
+        .collect();
+    lines.iter()
+        .map(|line| line.strip_prefix(' ').unwrap_or(line))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// A list of attributes, given as the nested items of a macro argument
+#[derive(Debug, Default)]
+struct AttrList(Vec<Meta>);
+
+impl FromMeta for AttrList {
+    fn from_list(items: &[NestedMeta]) -> darling::Result<Self> {
+        items.iter()
+            .map(|item| match item {
+                NestedMeta::Meta(meta) => Ok(meta.clone()),
+                NestedMeta::Lit(lit) => Err(darling::Error::unexpected_lit_type(lit)),
+            })
+            .collect::<darling::Result<_>>()
+            .map(AttrList)
+    }
+}
+
+/// The value of an integer literal argument, which may be negative.
+fn int_literal(expr: &syn::Expr) -> darling::Result<i128> {
+    match expr {
+        syn::Expr::Lit(syn::ExprLit { lit: syn::Lit::Int(int), .. }) => Ok(int.base10_parse()?),
+        syn::Expr::Unary(syn::ExprUnary { op: syn::UnOp::Neg(_), expr, .. }) => Ok(-int_literal(expr)?),
+        syn::Expr::Group(group) => int_literal(&group.expr),
+        syn::Expr::Paren(paren) => int_literal(&paren.expr),
+        _ => Err(darling::Error::custom("expected an integer literal").with_span(expr)),
+    }
+}
+
+/// A list of integers, given as an array of literals, e.g. `also = [5, -6]`
+#[derive(Debug, Default)]
+struct RawValues(Vec<i128>);
+
+impl FromMeta for RawValues {
+    fn from_expr(expr: &syn::Expr) -> darling::Result<Self> {
+        match expr {
+            syn::Expr::Array(array) => array.elems.iter().map(int_literal).collect::<darling::Result<_>>().map(RawValues),
+            _ => Ok(RawValues(vec![int_literal(expr)?])),
+        }
+    }
+}
+
+/// How a variant of the rustified enum without an associated constant converts to the FFI
+/// enum, given as `skip` or `skip = <raw value>`
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum Skip {
+    /// The conversion to the FFI enum fails for this variant
+    Fallible,
+    /// The variant converts to this raw value
+    Value(i128),
+}
+
+impl FromMeta for Skip {
+    fn from_word() -> darling::Result<Self> {
+        Ok(Skip::Fallible)
+    }
+
+    fn from_expr(expr: &syn::Expr) -> darling::Result<Self> {
+        int_literal(expr).map(Skip::Value)
+    }
+}
+
+/// The attributes of the FFI newtype, and the impls which replace some of its derives.
+struct NewtypeAttrs {
+    attrs: TokenStream,
+    /// A `Default` impl, if it is inherited from the enum
+    default_impl: Option<TokenStream>,
+    /// Whether `Debug` should be implemented
+    debug: bool,
+}
+
+fn newtype_attrs(item_enum: &ItemEnum, macro_args: &MacroArgs) -> Result<NewtypeAttrs, syn::Error> {
+    let mut derives: Vec<Path> = match &macro_args.newtype_derive {
+        Some(derives) => derives.to_vec(),
+        None if macro_args.inherit_derives => vec![],
+        None => {
+            let default_derives: Punctuated<Path, Token![,]> = parse_quote! { Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash };
+            default_derives.into_iter().collect()
+        }
+    };
+    let mut default_impl = None;
+    if macro_args.inherit_derives {
+        for attr in item_enum.attrs.iter().filter(|attr| attr.path().is_ident("derive")) {
+            let enum_derives = attr.parse_args_with(Punctuated::<Path, Token![,]>::parse_terminated)?;
+            for derive in enum_derives {
+                if derive.segments.last().is_some_and(|segment| segment.ident == "Default") {
+                    // A derived `Default` on the newtype would be `0`, rather than the default variant.
+                    let default_variant = item_enum.variants.iter()
+                        .find(|variant| variant.attrs.iter().any(|attr| attr.path().is_ident("default")))
+                        .ok_or(syn::Error::new(derive.span(), "`Default` requires a `#[default]` variant"))?;
+                    let original_ident = &item_enum.ident;
+                    let default_ident = constant_ident(default_variant, &VariantArgs::from_variant(default_variant)?, macro_args)?;
+                    default_impl = Some(quote! {
+                        #[allow(deprecated)]
+                        impl Default for #original_ident {
+                            fn default() -> Self {
+                                #original_ident::#default_ident
+                            }
+                        }
+                    });
+                } else if !derives.contains(&derive) {
+                    derives.push(derive);
+                }
+            }
+        }
+    }
+    let is_debug = |derive: &Path| derive.segments.last().is_some_and(|segment| segment.ident == "Debug");
+    let debug = derives.iter().any(is_debug);
+    derives.retain(|derive| !is_debug(derive));
+    let extra_attrs = &macro_args.newtype_attr.0;
+    let attrs = quote! {
+        #[derive(#(#derives),*)]
+        #( #[#extra_attrs] )*
+    };
+    Ok(NewtypeAttrs { attrs, default_impl, debug })
+}
+
+impl MacroArgs {
+    /// Replaces `{}` in the names given as arguments by `enum_name`, and enables the options
+    /// implied by others.
+    fn resolve(&mut self, enum_name: &Ident) {
+        let names = [&mut self.rust_enum_name, &mut self.catch_all, &mut self.strip_prefix, &mut self.const_strip_prefix];
+        for name in names.into_iter().flatten() {
+            *name = name.replace("{}", &enum_name.to_string());
+        }
+        self.non_zero |= self.status;
+    }
+}
+
+/// The name of the associated constant of `variant`.
+fn constant_ident(variant: &Variant, variant_args: &VariantArgs, macro_args: &MacroArgs) -> Result<Ident, syn::Error> {
+    renamed_ident(
+        "associated constant",
+        &variant.ident,
+        variant_args.const_rename.as_deref(),
+        macro_args.const_strip_prefix.as_deref(),
+        macro_args.const_rename_all,
+    )
+}
+
+/// Evaluates the discriminant of `variant` and collects what the generated items need to know
+/// about it.
+fn ffi_variant(variant: &Variant, discriminants: &mut DiscriminantEvaluator, macro_args: &MacroArgs) -> Result<FfiVariant, syn::Error> {
+    let non_zero = macro_args.non_zero;
+    let discriminant = discriminants.push_variant(variant)?.clone();
+    let variant_args = VariantArgs::from_variant(variant)?;
+    let success = macro_args.status && match &macro_args.success {
+        Some(success) => variant.ident == success,
+        None => discriminant.is_zero(),
+    };
+    if success && !discriminant.is_zero() {
+        return Err(syn::Error::new(variant.span(), "the success variant of a `status` enum must have the value 0"));
+    }
+    // The discriminant of a skipped variant is not a raw value of the FFI enum.
+    if non_zero && discriminant.is_zero() && variant_args.skip.is_none() && !success {
+        return Err(syn::Error::new(variant.span(), "discriminant must not be zero for NonZero representation"));
+    }
+    if !variant.fields.is_empty() {
+        return Err(syn::Error::new(variant.fields.span(), "FFI Enum variants may not contain fields"));
+    }
+    let cfg = cfg_predicate(&variant.attrs)?.map(|predicate| quote! { #[cfg(#predicate)] });
+    if variant_args.skip.is_some() && (!variant_args.alias.is_empty() || !variant_args.also.0.is_empty()) {
+        return Err(syn::Error::new(variant.span(), "skipped variants have no raw value, so they can't have `alias` or `also`"));
+    }
+    if non_zero && matches!(variant_args.skip, Some(Skip::Value(0))) {
+        return Err(syn::Error::new(variant.span(), "`skip` value must not be zero for NonZero representation"));
+    }
+    let aliases = variant_args.alias.iter()
+        .map(|alias| syn::parse_str::<Ident>(alias)
+            .map_err(|_| syn::Error::new(variant.ident.span(), format!("`{alias}` is not a valid name for an alias"))))
+        .collect::<Result<_, _>>()?;
+    if non_zero && variant_args.also.0.contains(&0) {
+        return Err(syn::Error::new(variant.span(), "`also` values must not be zero for NonZero representation"));
+    }
+    let rust_ident = renamed_ident(
+        "rustified variant",
+        &variant.ident,
+        variant_args.rename.as_deref(),
+        macro_args.strip_prefix.as_deref(),
+        macro_args.rename_all,
+    )?;
+    let ident = constant_ident(variant, &variant_args, macro_args)?;
+    Ok(FfiVariant {
+        ident,
+        rust_ident,
+        cfg: cfg.unwrap_or_default(),
+        display: variant_args.display,
+        doc: doc_string(&variant.attrs),
+        discriminant,
+        aliases,
+        also: variant_args.also.0,
+        skip: variant_args.skip,
+        success,
+    })
+}
+
+/// Checks that the `also` values fit the representation, and that every raw value converts to
+/// a single variant.
+fn check_also_values(variants: &[FfiVariant], int_repr: IntType) -> Result<(), syn::Error> {
+    let mut seen: Vec<(i128, &Ident)> = variants.iter()
+        .filter_map(|variant| match variant.discriminant {
+            Discriminant::Value(value) => Some((value, &variant.ident)),
+            Discriminant::Expr { .. } => None,
+        })
+        .collect();
+    for variant in variants {
+        for &value in &variant.also {
+            if !int_repr.contains(value) {
+                return Err(syn::Error::new(
+                    variant.ident.span(),
+                    format!("`also` value `{value}` is out of range for `{int_repr}`"),
+                ));
+            }
+            if let Some((_, existing)) = seen.iter().find(|(seen_value, _)| *seen_value == value) {
+                return Err(syn::Error::new(
+                    variant.ident.span(),
+                    format!("`also` value `{value}` already converts to `{existing}`"),
+                ));
+            }
+            seen.push((value, &variant.ident));
+        }
+    }
+    Ok(())
+}
+
+/// The FFI newtype of an enum.
+pub(crate) enum Newtype {
+    /// The newtype is generated, with a field of this type
+    Generated(TokenStream),
+    /// The newtype and its constants exist already, e.g. generated by bindgen, with a field of
+    /// this type
+    Existing(TokenStream),
+}
+
+fn enum_ffi_newtype(item_enum: ItemEnum, attr_args: &[NestedMeta]) -> Result<TokenStream, syn::Error> {
+    let enum_ident = item_enum.ident.clone();
+    let macro_args = || -> Result<MacroArgs, syn::Error> {
+        let mut macro_args = MacroArgs::from_list(attr_args)?;
+        macro_args.resolve(&enum_ident);
+        Ok(macro_args)
+    };
+    let reprs = enum_reprs(&item_enum.attrs)?;
+    if let [EnumRepr { cfg: None, kind }] = reprs.as_slice() {
+        let (int_repr, field_type) = newtype_repr(&item_enum, *kind)?;
+        return expand(item_enum, macro_args()?, Newtype::Generated(field_type), int_repr);
+    }
+    if reprs.is_empty() {
+        return Err(syn::Error::new(item_enum.span(), "No `repr` attribute found."));
+    }
+
+    // The representation differs between targets, so there is a complete expansion for each
+    // `cfg_attr` repr, and one for the unconditional repr on all other targets. Predicates may
+    // overlap, so each expansion is also gated on the earlier predicates not holding, and the
+    // first matching repr wins.
+    let mut output = TokenStream::new();
+    let mut predicates = vec![];
+    let mut fallback = None;
+    for EnumRepr { cfg, kind } in reprs {
+        let (int_repr, field_type) = newtype_repr(&item_enum, kind)?;
+        let expanded = expand(item_enum.clone(), macro_args()?, Newtype::Generated(field_type), int_repr)?;
+        match cfg {
+            Some(predicate) => {
+                let first_match = if predicates.is_empty() {
+                    predicate.clone()
+                } else {
+                    quote! { all(#predicate, not(any(#(#predicates),*))) }
+                };
+                output.extend(cfg_gated(expanded, &first_match)?);
+                predicates.push(predicate);
+            }
+            None => fallback = Some(expanded),
+        }
+    }
+    let otherwise = quote! { not(any(#(#predicates),*)) };
+    match fallback {
+        Some(expanded) => output.extend(cfg_gated(expanded, &otherwise)?),
+        None => {
+            let message = format!("`{}` has no `repr` for this target", item_enum.ident);
+            output.extend(quote! {
+                #[cfg(#otherwise)]
+                compile_error!(#message);
+            });
+        }
+    }
+    Ok(output)
+}
+
+/// Generates the FFI newtype of `item_enum`, the rustified enum, and the conversions between them.
+///
+/// `int_repr` is the integer type of the newtype field.
+fn expand(item_enum: ItemEnum, macro_args: MacroArgs, newtype: Newtype, int_repr: IntType) -> Result<TokenStream, syn::Error> {
+    let original_ident = item_enum.ident.clone();
+    let mut newtype_variants = vec![];
+    let mut ffi_variants = vec![];
+    let mut skipped_variants = vec![];
+    let mut rust_variant_idents = vec![];
+    let mut success_variant = None;
+    let mut cbindgen_constants = vec![];
+
+
+    // The representation.
+    let (base_repr_tokens, existing_newtype) = match newtype {
+        Newtype::Generated(field_type) => (field_type, false),
+        Newtype::Existing(field_type) => (field_type, true),
+    };
+    if existing_newtype && macro_args.status {
+        return Err(syn::Error::new(
+            proc_macro2::Span::call_site(),
+            "`status` can't be used with an existing newtype",
+        ));
+    }
+    if macro_args.success.is_some() && !macro_args.status {
+        return Err(syn::Error::new(proc_macro2::Span::call_site(), "`success` requires `status`"));
+    }
+    if existing_newtype && macro_args.non_zero {
+        return Err(syn::Error::new(
+            proc_macro2::Span::call_site(),
+            "`non_zero` can't be used with an existing newtype",
+        ));
+    }
+    if existing_newtype && macro_args.private_field {
+        return Err(syn::Error::new(
+            proc_macro2::Span::call_site(),
+            "`private_field` can't be used with an existing newtype",
+        ));
+    }
+    if existing_newtype && (macro_args.const_strip_prefix.is_some() || macro_args.const_rename_all.is_some()) {
+        return Err(syn::Error::new(
+            proc_macro2::Span::call_site(),
+            "the constants of an existing newtype can't be renamed",
+        ));
+    }
+    if existing_newtype && macro_args.cbindgen.is_some() {
+        return Err(syn::Error::new(
+            proc_macro2::Span::call_site(),
+            "`cbindgen` can't be used with an existing newtype",
+        ));
+    }
+
+    let mut discriminants = DiscriminantEvaluator::new(&original_ident, Some(int_repr));
+
+    let repr_tokens = if macro_args.non_zero {
+        quote! { core::num::NonZero<#base_repr_tokens> }
+    } else {
+        base_repr_tokens.clone()
+    };
+
+    for variant in &item_enum.variants {
+        let ffi_variant = ffi_variant(variant, &mut discriminants, &macro_args)?;
+        rust_variant_idents.push((!ffi_variant.success).then(|| ffi_variant.rust_ident.clone()));
+        if let Some(skip) = ffi_variant.skip {
+            if let Skip::Value(value) = skip {
+                if !int_repr.contains(value) {
+                    return Err(syn::Error::new(
+                        variant.ident.span(),
+                        format!("`skip` value `{value}` is out of range for `{int_repr}`"),
+                    ));
+                }
+            }
+            skipped_variants.push(ffi_variant);
+            continue;
+        }
+        if let Some(cbindgen_args) = &macro_args.cbindgen {
+            for alias in iter::once(None).chain(ffi_variant.aliases.iter().map(Some)) {
+                cbindgen_constants.push(cbindgen_args.constant(
+                    &original_ident,
+                    &item_enum.vis,
+                    &base_repr_tokens,
+                    macro_args.non_zero,
+                    &ffi_variant,
+                    alias,
+                ));
+            }
+        }
+        if ffi_variant.success {
+            success_variant = Some(ffi_variant);
+            continue;
+        }
+        let variant_ident = &ffi_variant.ident;
+        let curr_discriminant = &ffi_variant.discriminant;
+
+        let value = if macro_args.non_zero {
+            quote! { const { #original_ident(core::num::NonZero::new(#curr_discriminant).unwrap()) } }
+        } else {
+            quote! { #original_ident(#curr_discriminant) }
+        };
+        let forwarded_attrs: Vec<_> = variant.attrs.iter()
+            .filter(|attr| is_forwarded_variant_attr(attr))
+            .map(forwarded_variant_attr)
+            .collect();
+        let value_doc_separator = variant.attrs.iter()
+            .any(|attr| attr.path().is_ident("doc"))
+            .then(|| quote! { #[doc = ""] });
+        let value_doc = match curr_discriminant {
+            Discriminant::Value(value) if *value > 9 => format!("Value: `{value}` (`{value:#x}`)"),
+            discriminant => format!("Value: `{discriminant}`"),
+        };
+        let also_doc = (!ffi_variant.also.is_empty()).then(|| {
+            let values: Vec<_> = ffi_variant.also.iter().map(|value| format!("`{value}`")).collect();
+            let doc = format!("Also converted from: {}", values.join(", "));
+            quote! { #[doc = ""] #[doc = #doc] }
+        });
+        newtype_variants.push(
+            quote!{
+                    #(#forwarded_attrs)*
+                    #value_doc_separator
+                    #[doc = #value_doc]
+                    #also_doc
+                    pub const #variant_ident: #original_ident = #value;
+                }
+        );
+        let alias_doc = format!("Alias of [`Self::{variant_ident}`].");
+        for alias in &ffi_variant.aliases {
+            let forwarded_attrs = forwarded_attrs.iter().filter(|attr| !attr.path().is_ident("doc"));
+            newtype_variants.push(quote! {
+                #(#forwarded_attrs)*
+                #[doc = #alias_doc]
+                pub const #alias: #original_ident = #value;
+            });
+        }
+        ffi_variants.push(ffi_variant);
+    }
+    check_also_values(&ffi_variants, int_repr)?;
+    if macro_args.status && success_variant.is_none() {
+        let message = match &macro_args.success {
+            Some(success) => format!("`{original_ident}` has no variant `{success}`"),
+            None => format!("`status` requires a success variant with the value 0, but `{original_ident}` has none"),
+        };
+        return Err(syn::Error::new(original_ident.span(), message));
+    }
+
+    let vis = &item_enum.vis;
+
+    let mut rust_enum = item_enum.clone();
+    // Spell out every discriminant, since the original expressions may refer to variants
+    // via `Self::`, which the rustified enum can't evaluate.
+    let rust_variants = mem::take(&mut rust_enum.variants).into_iter()
+        .zip(discriminants.discriminants())
+        .zip(rust_variant_idents);
+    for ((mut variant, (_, discriminant)), rust_variant_ident) in rust_variants {
+        // The success variant of a `status` enum is not an error.
+        let Some(rust_variant_ident) = rust_variant_ident else {
+            continue;
+        };
+        variant.ident = rust_variant_ident;
+        variant.discriminant = Some((Default::default(), parse_quote! { #discriminant }));
+        variant.attrs.retain(|attr| !attr.path().is_ident("enum_ffi"));
+        rust_enum.variants.push(variant);
+    }
+    let rust_enum_ident = macro_args.rust_enum_name.as_ref()
+        .map(|name| format_ident!("{}", name) )
+        .unwrap_or(format_ident!("{}Rustified", original_ident));
+    rust_enum.ident = rust_enum_ident.clone();
+    // `repr(C, u8)` and per-target reprs are resolved to the integer type of the newtype.
+    strip_reprs(&mut rust_enum.attrs)?;
+    let int_repr_ident = format_ident!("{}", int_repr.to_string());
+    rust_enum.attrs.insert(0, parse_quote! { #[repr(#int_repr_ident)] });
+    if macro_args.exhaustive && (macro_args.catch_all.is_some() || macro_args.keep_value) {
+        return Err(syn::Error::new(
+            proc_macro2::Span::call_site(),
+            "`exhaustive` enums have no catch-all variant, so `catch_all` and `keep_value` can't be used",
+        ));
+    }
+    if macro_args.flags && macro_args.non_zero {
+        return Err(syn::Error::new(
+            proc_macro2::Span::call_site(),
+            "`flags` enums can be empty, so they can't use a `non_zero` representation",
+        ));
+    }
+    let catch_all = match &macro_args.catch_all {
+        _ if macro_args.exhaustive => None,
+        Some(catch_all_variant) => Some(CatchAll {
+            ident: format_ident!("{}", catch_all_variant),
+            injected: !rust_enum.variants.iter().any(|variant| variant.ident == catch_all_variant),
+            keep_value: macro_args.keep_value,
+        }),
+        None => Some(CatchAll {
+            ident: format_ident!("UnknownVariant{}", original_ident),
+            injected: true,
+            keep_value: macro_args.keep_value,
+        }),
+    };
+    let catch_all_fields = if macro_args.keep_value {
+        Fields::Unnamed(parse_quote! { (#repr_tokens) })
+    } else {
+        Fields::Unit
+    };
+    match &catch_all {
+        Some(CatchAll { ident: catch_all_ident, injected: true, .. }) => {
+            // `also` values already convert to their variant, so the catch-all can't use them.
+            let also_values: Vec<_> = ffi_variants.iter().flat_map(|variant| variant.also.iter().copied()).collect();
+            let discriminant = discriminants.unused_value(item_enum.ident.span(), &also_values, macro_args.non_zero)?
+                .map(|value| {
+                    let value = Discriminant::Value(value);
+                    (Default::default(), parse_quote! { #value })
+                });
+            rust_enum.variants.push(Variant {
+                attrs: vec![],
+                ident: catch_all_ident.clone(),
+                fields: catch_all_fields,
+                discriminant,
+            });
+        }
+        Some(CatchAll { ident: catch_all_ident, keep_value: true, .. }) => {
+            // An existing catch-all variant is decoded like any other unknown value, so that it
+            // round-trips with its own raw value.
+            let catch_all_variant = rust_enum.variants.iter_mut()
+                .find(|variant| &variant.ident == catch_all_ident)
+                .unwrap();
+            catch_all_variant.fields = catch_all_fields;
+            ffi_variants.retain(|variant| &variant.rust_ident != catch_all_ident);
+            skipped_variants.retain(|variant| &variant.rust_ident != catch_all_ident);
+        }
+        _ => {}
+    }
+
+    let catch_all_to_ffi_conversion = match &catch_all {
+        Some(CatchAll { ident: catch_all_ident, keep_value: true, .. }) => {
+            quote! { #rust_enum_ident::#catch_all_ident(raw) => #original_ident(raw), }
+        }
+        Some(CatchAll { ident: catch_all_ident, injected: true, .. }) if macro_args.non_zero => quote! {
+            #rust_enum_ident::#catch_all_ident => #original_ident(const {
+                core::num::NonZero::new(#rust_enum_ident::#catch_all_ident as #base_repr_tokens).unwrap()
+            }),
+        },
+        Some(CatchAll { ident: catch_all_ident, injected: true, .. }) => {
+            quote! { #rust_enum_ident::#catch_all_ident => #original_ident(#rust_enum_ident::#catch_all_ident as #repr_tokens), }
+        }
+        // An existing catch-all variant is converted via its own constant.
+        _ => quote! {},
+    };
+
+    // `From` and `TryFrom` can't both be implemented, due to the blanket `TryFrom` impl for
+    // `From` types, so only exhaustive enums implement `TryFrom`.
+    let ffi_to_rust_enum_conversion = if catch_all.is_some() {
+        quote! {
+            impl From<#original_ident> for #rust_enum_ident {
+                fn from(value: #original_ident) -> Self {
+                    value.to_rust()
+                }
+            }
+        }
+    } else {
+        let try_from_error_ident = format_ident!("TryFrom{}Error", original_ident);
+        let try_from_error_doc = format!("The error returned when converting an unknown [`{original_ident}`] value to [`{rust_enum_ident}`].");
+        let try_from_error_display = format!("unknown `{original_ident}` value: {{}}");
+        quote! {
+            impl TryFrom<#original_ident> for #rust_enum_ident {
+                type Error = #try_from_error_ident;
+
+                fn try_from(value: #original_ident) -> Result<Self, Self::Error> {
+                    value.try_to_rust().ok_or(#try_from_error_ident(value.0))
+                }
+            }
+
+            #[doc = #try_from_error_doc]
+            #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+            #vis struct #try_from_error_ident(pub #repr_tokens);
+
+            impl core::fmt::Display for #try_from_error_ident {
+                fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                    write!(f, #try_from_error_display, self.0)
+                }
+            }
+
+            impl core::error::Error for #try_from_error_ident {}
+        }
+    };
+
+    let newtype_docs = item_enum.attrs.iter().filter(|attr| attr.path().is_ident("doc"));
+    let NewtypeAttrs { attrs: newtype_attrs, default_impl: newtype_default_impl, debug } =
+        newtype_attrs(&item_enum, &macro_args)?;
+
+    let ffi_enum = FfiEnum {
+        original_ident: original_ident.clone(),
+        rust_enum_ident: rust_enum_ident.clone(),
+        vis: vis.clone(),
+        repr_tokens: repr_tokens.clone(),
+        base_repr_tokens: base_repr_tokens.clone(),
+        non_zero: macro_args.non_zero,
+        variants: ffi_variants,
+        skipped: skipped_variants,
+        catch_all,
+        cbindgen: macro_args.cbindgen.is_some(),
+    };
+    let debug_impl = (debug && !existing_newtype).then(|| fmt::debug_impl(&ffi_enum, macro_args.flags));
+    let cbindgen_ignore = ffi_enum.cbindgen_ignore();
+    let field_vis = (!macro_args.private_field).then(|| quote! { pub });
+    let newtype_definition = (!existing_newtype).then(|| quote! {
+        #(#newtype_docs)*
+        #[repr(transparent)]
+        #newtype_attrs
+        #vis struct #original_ident(#field_vis #repr_tokens);
+
+        #cbindgen_ignore
+        #[allow(non_upper_case_globals)]
+        impl #original_ident {
+            #(#newtype_variants)*
+        }
+
+        #(#cbindgen_constants)*
+
+        #newtype_default_impl
+    });
+    let display_impls = macro_args.display.map(|style| fmt::display_impls(&ffi_enum, style));
+    let radix_impls = fmt::radix_impls(&ffi_enum);
+    let flags_impl = macro_args.flags.then(|| flags::flags_impl(&ffi_enum));
+    let raw_impls = macro_args.private_field.then(|| raw::raw_impls(&ffi_enum));
+    let status_impls = success_variant.as_ref().map(|success| status::status_impls(&ffi_enum, success));
+    let serde_impls = macro_args.serde.map(|encoding| serde::serde_impls(&ffi_enum, encoding)).transpose()?;
+    let variant_tables = macro_args.variants.then(|| variants::variant_tables(&ffi_enum));
+    let names = macro_args.names
+        || macro_args.name_case_insensitive
+        || macro_args.name_aliases
+        || macro_args.serde.is_some();
+    let name_impls = names.then(|| names::name_impls(&ffi_enum, NameOptions {
+        case_insensitive: macro_args.name_case_insensitive,
+        aliases: macro_args.name_aliases,
+    })).transpose()?;
+
+    let to_rust_impls = convert::to_rust_impls(&ffi_enum);
+    let newtype_variant_idents: Vec<_> = ffi_enum.variants.iter().map(|variant| &variant.ident).collect();
+    let rust_variant_idents: Vec<_> = ffi_enum.variants.iter().map(|variant| &variant.rust_ident).collect();
+    let newtype_variant_cfgs: Vec<_> = ffi_enum.variants.iter().map(|variant| &variant.cfg).collect();
+    let skipped_to_ffi_error_ident = format_ident!("TryFrom{}Error", rust_enum_ident);
+    let skipped_to_ffi_conversions = ffi_enum.skipped.iter().map(|variant| {
+        let FfiVariant { rust_ident, cfg, .. } = variant;
+        let value = match variant.skip {
+            Some(Skip::Value(value)) => ffi_enum.ffi_value(Discriminant::Value(value).into_token_stream()),
+            _ => {
+                let name = rust_ident.unraw().to_string();
+                quote! { return Err(#skipped_to_ffi_error_ident(#name)) }
+            }
+        };
+        quote! { #cfg #rust_enum_ident::#rust_ident => #value, }
+    });
+    let to_ffi_match = quote! {
+        match self {
+            #( #newtype_variant_cfgs #rust_enum_ident::#rust_variant_idents => #original_ident::#newtype_variant_idents, )*
+            #( #skipped_to_ffi_conversions )*
+            #catch_all_to_ffi_conversion
+        }
+    };
+    let to_ffi_impls = if ffi_enum.fallible_to_ffi() {
+        let error_doc = format!(
+            "The error returned when converting a [`{rust_enum_ident}`] variant without a raw value to [`{original_ident}`]."
+        );
+        let error_display = format!("`{rust_enum_ident}::{{}}` has no `{original_ident}` value");
+        quote! {
+            #[allow(deprecated)]
+            impl #rust_enum_ident {
+                /// Converts to the FFI enum, failing for skipped variants without a raw value.
+                #vis const fn try_to_ffi(self) -> Result<#original_ident, #skipped_to_ffi_error_ident> {
+                    Ok(#to_ffi_match)
+                }
+            }
+
+            impl TryFrom<#rust_enum_ident> for #original_ident {
+                type Error = #skipped_to_ffi_error_ident;
+
+                fn try_from(value: #rust_enum_ident) -> Result<Self, Self::Error> {
+                    value.try_to_ffi()
+                }
+            }
+
+            #[doc = #error_doc]
+            #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+            #vis struct #skipped_to_ffi_error_ident(
+                /// The name of the variant
+                pub &'static str,
+            );
+
+            impl core::fmt::Display for #skipped_to_ffi_error_ident {
+                fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                    write!(f, #error_display, self.0)
+                }
+            }
+
+            impl core::error::Error for #skipped_to_ffi_error_ident {}
+        }
+    } else {
+        quote! {
+            #[allow(deprecated)]
+            impl #rust_enum_ident {
+                /// Converts to the FFI enum.
+                #vis const fn to_ffi(self) -> #original_ident {
+                    #to_ffi_match
+                }
+            }
+
+            impl From<#rust_enum_ident> for #original_ident {
+                fn from(value: #rust_enum_ident) -> Self {
+                    value.to_ffi()
+                }
+            }
+        }
+    };
+
+    Ok(quote! {
+        #rust_enum
+
+        #newtype_definition
+
+        #to_ffi_impls
+
+        #to_rust_impls
+
+        #ffi_to_rust_enum_conversion
+
+        #flags_impl
+
+        #raw_impls
+
+        #status_impls
+
+        #debug_impl
+
+        #display_impls
+
+        #radix_impls
+
+        #variant_tables
+
+        #name_impls
+
+        #serde_impls
+    })
+}
\ No newline at end of file
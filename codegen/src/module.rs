@@ -0,0 +1,156 @@
+//! Applying `enum_ffi` to all enums in a module.
+
+use darling::ast::NestedMeta;
+use darling::util::PathList;
+use darling::FromMeta;
+use proc_macro2::{Span, TokenStream};
+use quote::quote;
+use syn::spanned::Spanned;
+use syn::{Fields, Item, ItemEnum, ItemMod, Meta};
+use crate::repr::enum_reprs;
+use crate::{enum_ffi_newtype, MacroArgs};
+
+/// Selects enums by their name.
+#[derive(Debug, Clone)]
+pub(crate) enum Pattern {
+    Name(String),
+    #[cfg(feature = "regex")]
+    Regex(regex::Regex),
+}
+
+impl Pattern {
+    fn matches(&self, name: &str) -> bool {
+        match self {
+            Pattern::Name(pattern) => pattern == name,
+            #[cfg(feature = "regex")]
+            Pattern::Regex(regex) => regex.is_match(name),
+        }
+    }
+}
+
+/// The enums to convert.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct Selection {
+    /// Only these enums are converted, if given
+    pub(crate) include: Option<Vec<Pattern>>,
+    /// These enums are left untouched
+    pub(crate) exclude: Vec<Pattern>,
+}
+
+impl Selection {
+    /// Takes the selection out of the module arguments, leaving the defaults for `enum_ffi`.
+    pub(crate) fn from_args(attr_args: &mut Vec<NestedMeta>) -> Result<Selection, syn::Error> {
+        let names = |meta: &Meta| -> Result<Vec<Pattern>, syn::Error> {
+            PathList::from_meta(meta)?.iter()
+                .map(|path| match path.get_ident() {
+                    Some(ident) => Ok(Pattern::Name(ident.to_string())),
+                    None => Err(syn::Error::new(path.span(), "expected the name of an enum")),
+                })
+                .collect()
+        };
+        let mut selection = Selection::default();
+        let mut defaults = vec![];
+        for arg in attr_args.drain(..) {
+            match &arg {
+                NestedMeta::Meta(meta) if meta.path().is_ident("include") => {
+                    selection.include = Some(names(meta)?);
+                }
+                NestedMeta::Meta(meta) if meta.path().is_ident("exclude") => {
+                    selection.exclude = names(meta)?;
+                }
+                _ => defaults.push(arg),
+            }
+        }
+        *attr_args = defaults;
+        Ok(selection)
+    }
+
+    pub(crate) fn includes(&self, item_enum: &ItemEnum) -> bool {
+        let name = item_enum.ident.to_string();
+        self.include.as_ref().is_none_or(|include| include.iter().any(|pattern| pattern.matches(&name)))
+            && !self.exclude.iter().any(|pattern| pattern.matches(&name))
+    }
+}
+
+/// Whether `item_enum` is a C-Style enum, which `enum_ffi` can be applied to.
+pub(crate) fn is_c_style(item_enum: &ItemEnum) -> bool {
+    enum_reprs(&item_enum.attrs).is_ok_and(|reprs| !reprs.is_empty())
+        && item_enum.variants.iter().all(|variant| matches!(variant.fields, Fields::Unit))
+}
+
+/// Removes the `#[enum_ffi(...)]` attributes of an enum, and returns the arguments they contain.
+pub(crate) fn take_enum_args(item_enum: &mut ItemEnum) -> Result<Vec<NestedMeta>, syn::Error> {
+    let mut enum_args = vec![];
+    let mut result = Ok(());
+    item_enum.attrs.retain(|attr| {
+        if attr.path().segments.last().is_none_or(|segment| segment.ident != "enum_ffi") {
+            return true;
+        }
+        match &attr.meta {
+            Meta::Path(_) => {}
+            Meta::List(list) => match NestedMeta::parse_meta_list(list.tokens.clone()) {
+                Ok(args) => enum_args.extend(args),
+                Err(err) => result = Err(err),
+            },
+            Meta::NameValue(name_value) => {
+                result = Err(syn::Error::new_spanned(name_value, "expected `#[enum_ffi(...)]`"));
+            }
+        }
+        false
+    });
+    result.map(|_| enum_args)
+}
+
+/// The arguments of `enum_ffi` for one enum, with `enum_args` replacing the defaults of the module.
+pub(crate) fn merge_args(defaults: &[NestedMeta], enum_args: Vec<NestedMeta>) -> Vec<NestedMeta> {
+    let overridden = |arg: &NestedMeta| match arg {
+        NestedMeta::Meta(meta) => enum_args.iter()
+            .any(|enum_arg| matches!(enum_arg, NestedMeta::Meta(enum_meta) if enum_meta.path() == meta.path())),
+        NestedMeta::Lit(_) => false,
+    };
+    let mut args: Vec<_> = defaults.iter().filter(|arg| !overridden(arg)).cloned().collect();
+    args.extend(enum_args);
+    args
+}
+
+/// Applies `enum_ffi` with the arguments `defaults` to the selected enums in `items`, including
+/// those in inline modules.
+pub(crate) fn rewrite_items(items: &mut [Item], selection: &Selection, defaults: &[NestedMeta]) -> Result<(), syn::Error> {
+    for item in items.iter_mut() {
+        let item_enum = match item {
+            Item::Enum(item_enum) => item_enum,
+            Item::Mod(ItemMod { content: Some((_, items)), .. }) => {
+                rewrite_items(items, selection, defaults)?;
+                continue;
+            }
+            _ => continue,
+        };
+        if !selection.includes(item_enum) {
+            continue;
+        }
+        if !is_c_style(item_enum) {
+            if selection.include.is_some() {
+                return Err(syn::Error::new(
+                    item_enum.ident.span(),
+                    "only C-Style enums with a `repr` attribute can be included",
+                ));
+            }
+            continue;
+        }
+        let enum_args = take_enum_args(item_enum)?;
+        let output = enum_ffi_newtype(item_enum.clone(), &merge_args(defaults, enum_args))?;
+        *item = Item::Verbatim(output);
+    }
+    Ok(())
+}
+
+pub(crate) fn enum_ffi_module(mut item_mod: ItemMod, mut attr_args: Vec<NestedMeta>) -> Result<TokenStream, syn::Error> {
+    let selection = Selection::from_args(&mut attr_args)?;
+    // Report invalid defaults even if there are no enums to apply them to.
+    MacroArgs::from_list(&attr_args)?;
+    let Some((_, items)) = &mut item_mod.content else {
+        return Err(syn::Error::new(Span::call_site(), "`enum_ffi_module` can only be applied to inline modules"));
+    };
+    rewrite_items(items, &selection, &attr_args)?;
+    Ok(quote! { #item_mod })
+}
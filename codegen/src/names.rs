@@ -0,0 +1,173 @@
+//! Lookup of variants by name.
+
+use heck::ToShoutySnakeCase;
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::ext::IdentExt;
+use crate::{CatchAll, FfiEnum, FfiVariant};
+
+/// How `from_name` and `FromStr` match names.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct NameOptions {
+    /// Ignore ASCII case
+    pub(crate) case_insensitive: bool,
+    /// Also accept the C-style names of a variant
+    pub(crate) aliases: bool,
+}
+
+/// Builds the lookup table for `from_name`, which also accepts the aliases of the variants, sorted by name so that it can be binary searched.
+fn name_table(
+    variants: &[FfiVariant],
+    canonical_name: impl Fn(&FfiVariant) -> String,
+    options: NameOptions,
+) -> Result<Vec<(String, &FfiVariant)>, syn::Error> {
+    let mut table: Vec<(String, &FfiVariant)> = vec![];
+    for variant in variants {
+        let mut names = vec![canonical_name(variant)];
+        names.extend(variant.aliases.iter().map(|alias| alias.to_string()));
+        if options.aliases {
+            names.push(variant.ident.to_string());
+            names.push(variant.rust_ident.unraw().to_string());
+            names.push(variant.ident.to_string().to_shouty_snake_case());
+            names.push(variant.rust_ident.unraw().to_string().to_shouty_snake_case());
+        }
+        for name in names {
+            let key = if options.case_insensitive { name.to_ascii_lowercase() } else { name };
+            match table.iter().find(|(existing, _)| existing == &key) {
+                Some((_, existing)) if existing.ident == variant.ident => {}
+                Some((_, existing)) => {
+                    return Err(syn::Error::new(
+                        variant.ident.span(),
+                        format!("the name `{key}` is ambiguous, it also refers to `{}`", existing.ident),
+                    ));
+                }
+                None => table.push((key, variant)),
+            }
+        }
+    }
+    table.sort_by(|(a, _), (b, _)| a.cmp(b));
+    Ok(table)
+}
+
+/// Generates `name`, `from_name` and `FromStr` for the FFI enum and the rustified enum.
+pub(crate) fn name_impls(ffi_enum: &FfiEnum, options: NameOptions) -> Result<TokenStream, syn::Error> {
+    let FfiEnum { original_ident, rust_enum_ident, vis, variants, .. } = ffi_enum;
+    let variant_idents: Vec<_> = variants.iter().map(|variant| &variant.ident).collect();
+    let variant_cfgs: Vec<_> = variants.iter().map(|variant| &variant.cfg).collect();
+    let ffi_names: Vec<_> = variants.iter().map(|variant| variant.ident.to_string()).collect();
+    let rust_idents: Vec<_> = variants.iter().map(|variant| &variant.rust_ident).collect();
+    let rust_names: Vec<_> = variants.iter().map(|variant| variant.rust_ident.unraw().to_string()).collect();
+    let raw_self = ffi_enum.raw_value(quote! { self });
+    let raw_variants: Vec<_> = variant_idents.iter()
+        .map(|ident| ffi_enum.raw_value(quote! { #original_ident::#ident }))
+        .collect();
+    let also_values = ffi_enum.also_values();
+    let also_cfgs = also_values.iter().map(|(variant, _)| &variant.cfg);
+    let also_names = also_values.iter().map(|(variant, _)| variant.ident.to_string());
+    let also_values = also_values.iter().map(|(_, value)| value);
+
+    let compare = if options.case_insensitive {
+        quote! { key.bytes().cmp(name.bytes().map(|byte| byte.to_ascii_lowercase())) }
+    } else {
+        quote! { (*key).cmp(name) }
+    };
+    let lookup = |table: Vec<(String, &FfiVariant)>| {
+        let keys = table.iter().map(|(key, _)| key);
+        let cfgs = table.iter().map(|(_, variant)| &variant.cfg);
+        let idents = table.iter().map(|(_, variant)| &variant.ident);
+        quote! {
+            const NAMES: &[(&str, #original_ident)] = &[#( #cfgs (#keys, #original_ident::#idents) ),*];
+            NAMES.binary_search_by(|(key, _)| #compare)
+                .ok()
+                .map(|index| #original_ident(NAMES[index].1.0))
+        }
+    };
+    let ffi_lookup = lookup(name_table(variants, |variant| variant.ident.to_string(), options)?);
+    let rust_lookup = lookup(name_table(variants, |variant| variant.rust_ident.unraw().to_string(), options)?);
+
+    let skipped_cfgs = ffi_enum.skipped.iter().map(|variant| &variant.cfg);
+    let skipped_idents = ffi_enum.skipped.iter().map(|variant| &variant.rust_ident);
+    let skipped_names = ffi_enum.skipped.iter().map(|variant| variant.rust_ident.unraw().to_string());
+    let catch_all_name_arm = match ffi_enum.extra_catch_all() {
+        Some(CatchAll { ident, keep_value, .. }) => {
+            let name = ident.to_string();
+            let fields = keep_value.then(|| quote! { (_) });
+            quote! { #rust_enum_ident::#ident #fields => #name, }
+        }
+        None => quote! {},
+    };
+
+    let parse_error_ident = format_ident!("Parse{}Error", original_ident);
+    let parse_error_doc = format!("The error returned when parsing an unknown [`{original_ident}`] name.");
+    let parse_error_display = format!("unknown `{original_ident}` name: {{:?}}");
+
+    Ok(quote! {
+        #[allow(deprecated)]
+        impl #original_ident {
+            /// The name of the associated constant with this value, or `None` for unknown values.
+            ///
+            /// `also` values have the name of the variant they convert to.
+            #vis const fn name(&self) -> Option<&'static str> {
+                match #raw_self {
+                    #( #variant_cfgs raw if raw == #raw_variants => Some(#ffi_names), )*
+                    #( #also_cfgs #also_values => Some(#also_names), )*
+                    _ => None,
+                }
+            }
+
+            /// Looks up the associated constant with the given name.
+            #vis fn from_name(name: &str) -> Option<Self> {
+                #ffi_lookup
+            }
+        }
+
+        #[allow(deprecated)]
+        impl #rust_enum_ident {
+            /// The name of the variant.
+            #vis const fn name(&self) -> &'static str {
+                match self {
+                    #( #variant_cfgs #rust_enum_ident::#rust_idents => #rust_names, )*
+                    #( #skipped_cfgs #rust_enum_ident::#skipped_idents => #skipped_names, )*
+                    #catch_all_name_arm
+                }
+            }
+
+            /// Looks up the variant with the given name.
+            ///
+            /// A catch-all variant which is not declared in the original enum can't be looked up,
+            /// and neither can skipped variants.
+            #vis fn from_name(name: &str) -> Option<Self> {
+                let value: Option<#original_ident> = { #rust_lookup };
+                value.and_then(#original_ident::try_to_rust)
+            }
+        }
+
+        #[doc = #parse_error_doc]
+        #[derive(Debug, Clone, PartialEq, Eq)]
+        #vis struct #parse_error_ident(pub String);
+
+        impl core::fmt::Display for #parse_error_ident {
+            fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                write!(f, #parse_error_display, self.0)
+            }
+        }
+
+        impl core::error::Error for #parse_error_ident {}
+
+        impl core::str::FromStr for #original_ident {
+            type Err = #parse_error_ident;
+
+            fn from_str(name: &str) -> Result<Self, Self::Err> {
+                Self::from_name(name).ok_or_else(|| #parse_error_ident(name.into()))
+            }
+        }
+
+        impl core::str::FromStr for #rust_enum_ident {
+            type Err = #parse_error_ident;
+
+            fn from_str(name: &str) -> Result<Self, Self::Err> {
+                Self::from_name(name).ok_or_else(|| #parse_error_ident(name.into()))
+            }
+        }
+    })
+}
@@ -0,0 +1,62 @@
+//! Access to the raw value of an FFI enum whose field is private.
+
+use proc_macro2::TokenStream;
+use quote::quote;
+use crate::FfiEnum;
+
+/// Generates the constructors and accessors which replace the public field of the FFI enum.
+pub(crate) fn raw_impls(ffi_enum: &FfiEnum) -> TokenStream {
+    let FfiEnum { original_ident, vis, repr_tokens, base_repr_tokens, non_zero, .. } = ffi_enum;
+    let new_value = if *non_zero {
+        quote! {
+            let Some(raw) = core::num::NonZero::new(raw) else {
+                return None;
+            };
+            Self(raw)
+        }
+    } else {
+        quote! { Self(raw) }
+    };
+    let from_raw_unchecked = non_zero.then(|| quote! {
+        /// Creates a value from a raw value, which may be unknown.
+        ///
+        /// # Safety
+        ///
+        /// `raw` must not be zero.
+        #vis const unsafe fn from_raw_unchecked(raw: #base_repr_tokens) -> Self {
+            // SAFETY: The caller guarantees that `raw` is not zero.
+            Self(unsafe { core::num::NonZero::new_unchecked(raw) })
+        }
+    });
+
+    quote! {
+        impl #original_ident {
+            /// Creates a value from its raw representation, which may be unknown.
+            #vis const fn from_raw(raw: #repr_tokens) -> Self {
+                Self(raw)
+            }
+
+            /// The raw representation.
+            #vis const fn into_raw(self) -> #repr_tokens {
+                self.0
+            }
+
+            /// A reference to the raw representation.
+            #vis const fn as_raw(&self) -> &#repr_tokens {
+                &self.0
+            }
+
+            /// The value with the raw value `raw`, if it converts to a variant with
+            /// [`Self::try_to_rust`].
+            #vis const fn checked(raw: #base_repr_tokens) -> Option<Self> {
+                let value = { #new_value };
+                match value.try_to_rust() {
+                    Some(_) => Some(value),
+                    None => None,
+                }
+            }
+
+            #from_raw_unchecked
+        }
+    }
+}
@@ -0,0 +1,73 @@
+//! The names of the rustified variants and of the associated constants, which can both differ
+//! from the names of the original variants.
+
+use darling::FromMeta;
+use heck::{ToLowerCamelCase, ToShoutySnakeCase, ToSnakeCase, ToUpperCamelCase};
+use syn::ext::IdentExt;
+use syn::Ident;
+
+/// The case `rename_all` and `const_rename_all` convert the names to.
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum RenameRule {
+    Pascal,
+    Camel,
+    Snake,
+    ScreamingSnake,
+}
+
+impl FromMeta for RenameRule {
+    fn from_string(value: &str) -> darling::Result<Self> {
+        match value {
+            "PascalCase" => Ok(RenameRule::Pascal),
+            "camelCase" => Ok(RenameRule::Camel),
+            "snake_case" => Ok(RenameRule::Snake),
+            "SCREAMING_SNAKE_CASE" => Ok(RenameRule::ScreamingSnake),
+            _ => Err(darling::Error::unknown_value(value)),
+        }
+    }
+}
+
+impl RenameRule {
+    fn apply(self, name: &str) -> String {
+        match self {
+            RenameRule::Pascal => name.to_upper_camel_case(),
+            RenameRule::Camel => name.to_lower_camel_case(),
+            RenameRule::Snake => name.to_snake_case(),
+            RenameRule::ScreamingSnake => name.to_shouty_snake_case(),
+        }
+    }
+}
+
+/// The name of the `kind` generated for the original variant `ident`, e.g. its rustified variant.
+///
+/// `rename` replaces the name entirely. Otherwise `strip_prefix` is removed from the name,
+/// if it has the prefix, before it is converted by `rename_all`.
+pub(crate) fn renamed_ident(
+    kind: &str,
+    ident: &Ident,
+    rename: Option<&str>,
+    strip_prefix: Option<&str>,
+    rename_all: Option<RenameRule>,
+) -> Result<Ident, syn::Error> {
+    let name = match rename {
+        Some(rename) => rename.to_owned(),
+        None if strip_prefix.is_none() && rename_all.is_none() => return Ok(ident.clone()),
+        None => {
+            let name = ident.unraw().to_string();
+            let stripped = strip_prefix.and_then(|prefix| name.strip_prefix(prefix)).unwrap_or(&name);
+            match rename_all {
+                Some(rule) => rule.apply(stripped),
+                None => stripped.to_owned(),
+            }
+        }
+    };
+    // Keywords, e.g. `type` for `FOO_TYPE` in snake_case, become raw identifiers.
+    let mut renamed = syn::parse_str::<Ident>(&name)
+        .or_else(|_| syn::parse_str::<Ident>(&format!("r#{name}")))
+        .map_err(|_| syn::Error::new(
+            ident.span(),
+            format!("the {kind} of `{ident}` would be named `{name}`, which is not a valid identifier"),
+        ))?;
+    renamed.set_span(ident.span());
+    Ok(renamed)
+}
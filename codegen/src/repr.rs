@@ -0,0 +1,307 @@
+//! The integer representation of an enum.
+
+use std::fmt;
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::punctuated::Punctuated;
+use syn::spanned::Spanned;
+use syn::{parse_quote, AttrStyle, Attribute, Meta, Token, Type};
+
+/// A primitive integer type, as used in `#[repr(..)]` or as the target of an `as` cast.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum IntType {
+    U8,
+    U16,
+    U32,
+    U64,
+    U128,
+    Usize,
+    I8,
+    I16,
+    I32,
+    I64,
+    I128,
+    Isize,
+}
+
+impl IntType {
+    pub(crate) fn from_name(name: &str) -> Option<IntType> {
+        let int_type = match name {
+            "u8" => IntType::U8,
+            "u16" => IntType::U16,
+            "u32" => IntType::U32,
+            "u64" => IntType::U64,
+            "u128" => IntType::U128,
+            "usize" => IntType::Usize,
+            "i8" => IntType::I8,
+            "i16" => IntType::I16,
+            "i32" => IntType::I32,
+            "i64" => IntType::I64,
+            "i128" => IntType::I128,
+            "isize" => IntType::Isize,
+            _ => return None,
+        };
+        Some(int_type)
+    }
+
+    pub(crate) fn from_type(ty: &Type) -> Option<IntType> {
+        match ty {
+            Type::Path(type_path) if type_path.qself.is_none() => {
+                IntType::from_name(&type_path.path.get_ident()?.to_string())
+            }
+            Type::Paren(paren) => IntType::from_type(&paren.elem),
+            Type::Group(group) => IntType::from_type(&group.elem),
+            _ => None,
+        }
+    }
+
+    /// The type of a `core::ffi` type alias like `c_uint`.
+    ///
+    /// Returns `None` for aliases whose width or signedness depends on the target, like `c_long`.
+    pub(crate) fn from_c_name(name: &str) -> Option<IntType> {
+        let int_type = match name {
+            "c_schar" => IntType::I8,
+            "c_uchar" => IntType::U8,
+            "c_short" => IntType::I16,
+            "c_ushort" => IntType::U16,
+            "c_int" => IntType::I32,
+            "c_uint" => IntType::U32,
+            "c_longlong" => IntType::I64,
+            "c_ulonglong" => IntType::U64,
+            _ => return None,
+        };
+        Some(int_type)
+    }
+
+    /// The type of the integer type `ty`, which may be a path to a primitive or to a
+    /// `core::ffi` type alias, as emitted by bindgen.
+    pub(crate) fn from_ffi_type(ty: &Type) -> Option<IntType> {
+        match ty {
+            Type::Path(type_path) if type_path.qself.is_none() => {
+                let name = type_path.path.segments.last()?.ident.to_string();
+                IntType::from_name(&name).or_else(|| IntType::from_c_name(&name))
+            }
+            Type::Paren(paren) => IntType::from_ffi_type(&paren.elem),
+            Type::Group(group) => IntType::from_ffi_type(&group.elem),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn is_signed(self) -> bool {
+        matches!(self, IntType::I8 | IntType::I16 | IntType::I32 | IntType::I64 | IntType::I128 | IntType::Isize)
+    }
+
+    /// The width of the type, or `None` if it depends on the target.
+    pub(crate) fn bits(self) -> Option<u32> {
+        match self {
+            IntType::U8 | IntType::I8 => Some(8),
+            IntType::U16 | IntType::I16 => Some(16),
+            IntType::U32 | IntType::I32 => Some(32),
+            IntType::U64 | IntType::I64 => Some(64),
+            IntType::U128 | IntType::I128 => Some(128),
+            IntType::Usize | IntType::Isize => None,
+        }
+    }
+
+    /// The smallest value of the type.
+    ///
+    /// `usize` and `isize` are assumed to be 64 bits wide, so that values which can't fit
+    /// on any target are still rejected early.
+    pub(crate) fn min(self) -> i128 {
+        if !self.is_signed() {
+            return 0;
+        }
+        match self.bits().unwrap_or(64) {
+            128 => i128::MIN,
+            bits => -(1 << (bits - 1)),
+        }
+    }
+
+    /// The largest value of the type.
+    ///
+    /// Discriminants of `u128` above `i128::MAX` are not tracked by the macro, but left for the
+    /// compiler to evaluate.
+    pub(crate) fn max(self) -> i128 {
+        match (self.bits().unwrap_or(64), self.is_signed()) {
+            (128, _) => i128::MAX,
+            (bits, true) => (1 << (bits - 1)) - 1,
+            (bits, false) => (1 << bits) - 1,
+        }
+    }
+
+    pub(crate) fn contains(self, value: i128) -> bool {
+        (self.min()..=self.max()).contains(&value)
+    }
+
+    /// Truncates `value` to this type, like an `as` cast would.
+    ///
+    /// Returns `None` if the result depends on the width of the target.
+    pub(crate) fn wrap(self, value: i128) -> Option<i128> {
+        let bits = self.bits()?;
+        if bits == 128 {
+            return if self.is_signed() || value >= 0 { Some(value) } else { None };
+        }
+        let truncated = value & ((1 << bits) - 1);
+        if self.is_signed() && truncated > self.max() {
+            Some(truncated - (1 << bits))
+        } else {
+            Some(truncated)
+        }
+    }
+}
+
+impl fmt::Display for IntType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            IntType::U8 => "u8",
+            IntType::U16 => "u16",
+            IntType::U32 => "u32",
+            IntType::U64 => "u64",
+            IntType::U128 => "u128",
+            IntType::Usize => "usize",
+            IntType::I8 => "i8",
+            IntType::I16 => "i16",
+            IntType::I32 => "i32",
+            IntType::I64 => "i64",
+            IntType::I128 => "i128",
+            IntType::Isize => "isize",
+        };
+        f.write_str(name)
+    }
+}
+
+/// The representation given by a `#[repr(...)]` attribute of an enum.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ReprKind {
+    /// `repr(u8)` etc., possibly combined with `C`
+    Int(IntType),
+    /// `repr(C)`, which is a C `int`, unless the discriminants don't fit
+    C,
+}
+
+/// A representation of an enum, which may only apply to some targets.
+#[derive(Debug, Clone)]
+pub(crate) struct EnumRepr {
+    /// The predicate of the `cfg_attr` the `repr` is given in
+    pub(crate) cfg: Option<TokenStream>,
+    pub(crate) kind: ReprKind,
+}
+
+/// The representations of an enum with the attributes `attrs`.
+///
+/// The items of all unconditional `repr` attributes are combined into one representation, like
+/// the compiler does. Each `repr` in a `cfg_attr` is a representation of its own.
+pub(crate) fn enum_reprs(attrs: &[Attribute]) -> Result<Vec<EnumRepr>, syn::Error> {
+    let mut unconditional = vec![];
+    let mut conditional = vec![];
+    for attr in attrs.iter().filter(|attr| matches!(attr.style, AttrStyle::Outer)) {
+        collect_reprs(&attr.meta, None, &mut unconditional, &mut conditional)?;
+    }
+    let mut reprs = vec![];
+    if let Some(kind) = repr_kind(&unconditional)? {
+        reprs.push(EnumRepr { cfg: None, kind });
+    }
+    for (cfg, metas) in conditional {
+        if let Some(kind) = repr_kind(&metas)? {
+            reprs.push(EnumRepr { cfg: Some(cfg), kind });
+        }
+    }
+    Ok(reprs)
+}
+
+/// Collects the items of the `repr` attribute `meta`, which applies if `cfg` holds.
+fn collect_reprs(
+    meta: &Meta,
+    cfg: Option<TokenStream>,
+    unconditional: &mut Vec<Meta>,
+    conditional: &mut Vec<(TokenStream, Vec<Meta>)>,
+) -> Result<(), syn::Error> {
+    let Meta::List(list) = meta else {
+        return Ok(());
+    };
+    if list.path.is_ident("repr") {
+        let items = list.parse_args_with(Punctuated::<Meta, Token![,]>::parse_terminated)?;
+        match cfg {
+            Some(cfg) => conditional.push((cfg, items.into_iter().collect())),
+            None => unconditional.extend(items),
+        }
+    } else if list.path.is_ident("cfg_attr") {
+        let mut args = list.parse_args_with(Punctuated::<Meta, Token![,]>::parse_terminated)?.into_iter();
+        let Some(predicate) = args.next() else {
+            return Ok(());
+        };
+        let cfg = match cfg {
+            Some(outer) => quote! { all(#outer, #predicate) },
+            None => quote! { #predicate },
+        };
+        for meta in args {
+            collect_reprs(&meta, Some(cfg.clone()), unconditional, conditional)?;
+        }
+    }
+    Ok(())
+}
+
+/// The representation given by the items `metas` of `repr` attributes, if there are any.
+fn repr_kind(metas: &[Meta]) -> Result<Option<ReprKind>, syn::Error> {
+    let mut kind = None;
+    for meta in metas {
+        let ident = meta.path().get_ident().map(|ident| ident.to_string()).unwrap_or_default();
+        match (ident.as_str(), IntType::from_name(&ident)) {
+            (_, Some(int_type)) if matches!(meta, Meta::Path(_)) => match kind {
+                Some(ReprKind::Int(_)) => {
+                    return Err(syn::Error::new(meta.span(), "conflicting integer types in `repr`"));
+                }
+                _ => kind = Some(ReprKind::Int(int_type)),
+            },
+            ("C", _) if matches!(meta, Meta::Path(_)) => {
+                kind.get_or_insert(ReprKind::C);
+            }
+            ("align" | "packed", _) => {
+                return Err(syn::Error::new(
+                    meta.span(),
+                    format!("`repr({ident})` changes the layout of the enum, so it can't be used for an FFI enum"),
+                ));
+            }
+            _ => return Err(syn::Error::new(meta.span(), "expected an integer type or `C` in `repr`")),
+        }
+    }
+    Ok(kind)
+}
+
+/// Removes the `repr` attributes from `attrs`, including those in `cfg_attr`.
+pub(crate) fn strip_reprs(attrs: &mut Vec<Attribute>) -> Result<(), syn::Error> {
+    let mut stripped = Vec::with_capacity(attrs.len());
+    for mut attr in attrs.drain(..) {
+        if let Some(meta) = strip_repr(attr.meta)? {
+            attr.meta = meta;
+            stripped.push(attr);
+        }
+    }
+    *attrs = stripped;
+    Ok(())
+}
+
+/// `meta` without `repr`, or `None` if nothing is left of it.
+fn strip_repr(meta: Meta) -> Result<Option<Meta>, syn::Error> {
+    if meta.path().is_ident("repr") {
+        return Ok(None);
+    }
+    let Meta::List(list) = &meta else {
+        return Ok(Some(meta));
+    };
+    if !list.path.is_ident("cfg_attr") {
+        return Ok(Some(meta));
+    }
+    let mut args = list.parse_args_with(Punctuated::<Meta, Token![,]>::parse_terminated)?.into_iter();
+    let Some(predicate) = args.next() else {
+        return Ok(Some(meta));
+    };
+    let mut remaining = vec![];
+    for arg in args {
+        remaining.extend(strip_repr(arg)?);
+    }
+    if remaining.is_empty() {
+        return Ok(None);
+    }
+    Ok(Some(parse_quote! { cfg_attr(#predicate, #(#remaining),*) }))
+}
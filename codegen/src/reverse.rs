@@ -0,0 +1,286 @@
+//! Deriving the rustified enum from the constants bindgen generates instead of an enum.
+
+use darling::ast::NestedMeta;
+use darling::FromMeta;
+use proc_macro2::{Span, TokenStream};
+use quote::{format_ident, quote, ToTokens};
+use syn::spanned::Spanned;
+use syn::{
+    parse_quote, Attribute, Expr, Fields, Ident, ImplItem, Item, ItemEnum, ItemImpl, ItemMod, ItemStruct, Type, UnOp,
+    Variant, Visibility,
+};
+use crate::discriminant::{Discriminant, DiscriminantEvaluator};
+use crate::repr::IntType;
+use crate::{expand, MacroArgs, Newtype};
+
+/// The arguments of `enum_ffi_rustify`, besides those of `enum_ffi`.
+#[derive(Debug, Default)]
+struct ReverseArgs {
+    /// The integer type of the constants
+    repr: Option<Type>,
+    /// The name of the newtype generated for a module of constants
+    newtype_name: Option<String>,
+}
+
+impl ReverseArgs {
+    /// Takes the arguments out of `attr_args`, leaving those of `enum_ffi`.
+    fn from_args(attr_args: &mut Vec<NestedMeta>) -> Result<ReverseArgs, syn::Error> {
+        let mut reverse_args = ReverseArgs::default();
+        let mut remaining = vec![];
+        for arg in attr_args.drain(..) {
+            match &arg {
+                NestedMeta::Meta(meta) if meta.path().is_ident("repr") => {
+                    let repr = String::from_meta(meta)?;
+                    reverse_args.repr = Some(syn::parse_str(&repr).map_err(|err| syn::Error::new(meta.span(), err))?);
+                }
+                NestedMeta::Meta(meta) if meta.path().is_ident("newtype_name") => {
+                    reverse_args.newtype_name = Some(String::from_meta(meta)?);
+                }
+                _ => remaining.push(arg),
+            }
+        }
+        *attr_args = remaining;
+        Ok(reverse_args)
+    }
+}
+
+/// A constant which becomes a variant of the enum.
+struct Constant {
+    attrs: Vec<Attribute>,
+    vis: Visibility,
+    ident: Ident,
+    value: Expr,
+}
+
+/// The enum equivalent to the constants, from which the rustified enum is generated.
+///
+/// An enum can't have two variants with the same discriminant, so constants with the value of
+/// an earlier constant become `alias`es of it.
+fn synthesize_enum(ident: &Ident, vis: Visibility, int_type: IntType, constants: Vec<Constant>) -> Result<ItemEnum, syn::Error> {
+    let mut discriminants = DiscriminantEvaluator::new(ident, Some(int_type));
+    let mut variants: Vec<(Variant, Option<i128>)> = vec![];
+    for Constant { attrs, ident, value, .. } in constants {
+        let variant: Variant = parse_quote! { #(#attrs)* #ident = #value };
+        let value = match discriminants.push_variant(&variant)? {
+            Discriminant::Value(value) => Some(*value),
+            // Values only known to the compiler can't be compared.
+            Discriminant::Expr { .. } => None,
+        };
+        match variants.iter_mut().find(|(_, existing)| value.is_some() && *existing == value) {
+            Some((original, _)) => {
+                let alias = ident.to_string();
+                original.attrs.push(parse_quote! { #[enum_ffi(alias = #alias)] });
+            }
+            None => variants.push((variant, value)),
+        }
+    }
+    let int_type = format_ident!("{}", int_type.to_string());
+    let variants = variants.into_iter().map(|(variant, _)| variant);
+    Ok(parse_quote! {
+        #[repr(#int_type)]
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        #vis enum #ident {
+            #( #variants, )*
+        }
+    })
+}
+
+/// The integer type of `ty`, which is needed as the `repr` of the rustified enum.
+fn int_type(ty: &Type) -> Result<IntType, syn::Error> {
+    IntType::from_ffi_type(ty).ok_or(syn::Error::new(
+        ty.span(),
+        "expected an integer type of a fixed width, use `repr = \"...\"` to specify it",
+    ))
+}
+
+/// Whether `expr` is a literal, which can be used as a discriminant outside of its module.
+fn is_literal(expr: &Expr) -> bool {
+    match expr {
+        Expr::Lit(_) => true,
+        Expr::Unary(unary) => matches!(unary.op, UnOp::Neg(_)) && is_literal(&unary.expr),
+        Expr::Paren(paren) => is_literal(&paren.expr),
+        Expr::Group(group) => is_literal(&group.expr),
+        _ => false,
+    }
+}
+
+/// The newtype an inherent `impl` block is for.
+fn impl_newtype(item_impl: &ItemImpl) -> Option<&Ident> {
+    match &*item_impl.self_ty {
+        Type::Path(type_path) if item_impl.trait_.is_none() && item_impl.generics.params.is_empty() => {
+            type_path.path.get_ident()
+        }
+        _ => None,
+    }
+}
+
+/// The constants of the newtype `ident` in `item_impl`.
+fn newtype_constants(ident: &Ident, item_impl: &ItemImpl) -> Result<Vec<Constant>, syn::Error> {
+    let is_newtype = |path: &syn::Path| path.is_ident(ident) || path.is_ident("Self");
+    let mut constants = vec![];
+    for item in &item_impl.items {
+        let ImplItem::Const(item_const) = item else {
+            continue;
+        };
+        if !matches!(&item_const.ty, Type::Path(type_path) if is_newtype(&type_path.path)) {
+            continue;
+        }
+        let value = match &item_const.expr {
+            Expr::Call(call) if call.args.len() == 1 && matches!(&*call.func, Expr::Path(func) if is_newtype(&func.path)) => {
+                call.args[0].clone()
+            }
+            expr => return Err(syn::Error::new(expr.span(), format!("expected `{ident}(value)`"))),
+        };
+        constants.push(Constant {
+            attrs: item_const.attrs.clone(),
+            vis: item_const.vis.clone(),
+            ident: item_const.ident.clone(),
+            value,
+        });
+    }
+    Ok(constants)
+}
+
+/// Derives the rustified enum from the constants of a bindgen `newtype_enum`, e.g.
+/// `impl Foo { pub const A: Foo = Foo(0); }`, which may be spread over several `impl` blocks.
+fn rustify_newtype(ident: &Ident, item_impls: &[&ItemImpl], repr: &Type, macro_args: MacroArgs) -> Result<TokenStream, syn::Error> {
+    let mut constants = vec![];
+    for item_impl in item_impls {
+        constants.extend(newtype_constants(ident, item_impl)?);
+    }
+    let vis = constants.first().map_or(Visibility::Inherited, |constant| constant.vis.clone());
+    let int_type = int_type(repr)?;
+    let item_enum = synthesize_enum(ident, vis, int_type, constants)?;
+    expand(item_enum, macro_args, Newtype::Existing(quote! { #repr }), int_type)
+}
+
+/// Derives the rustified enums of all bindgen newtypes in a module, as bindgen generates them
+/// with one `impl` block per constant, e.g.
+/// `struct Foo(pub u32); impl Foo { pub const A: Foo = Foo(0); } impl Foo { pub const B: Foo = Foo(1); }`.
+///
+/// The generated items are added to the module.
+fn rustify_newtypes(mut item_mod: ItemMod, attr_args: &[NestedMeta], reverse_args: ReverseArgs) -> Result<Item, syn::Error> {
+    let Some((_, items)) = &mut item_mod.content else {
+        return Err(syn::Error::new(Span::call_site(), "`enum_ffi_rustify` can only be applied to inline modules"));
+    };
+    let newtypes: Vec<(&ItemStruct, &Type)> = items.iter()
+        .filter_map(|item| match item {
+            Item::Struct(item_struct) if item_struct.generics.params.is_empty() => match &item_struct.fields {
+                Fields::Unnamed(fields) if fields.unnamed.len() == 1 => Some((item_struct, &fields.unnamed[0].ty)),
+                _ => None,
+            },
+            _ => None,
+        })
+        .collect();
+    let mut output = vec![];
+    for (item_struct, field_ty) in newtypes {
+        let ident = &item_struct.ident;
+        let item_impls: Vec<_> = items.iter()
+            .filter_map(|item| match item {
+                Item::Impl(item_impl) if impl_newtype(item_impl) == Some(ident) => Some(item_impl),
+                _ => None,
+            })
+            .collect();
+        // Newtypes without constants are no enums.
+        if item_impls.iter().all(|item_impl| matches!(newtype_constants(ident, item_impl), Ok(constants) if constants.is_empty())) {
+            continue;
+        }
+        let mut macro_args = MacroArgs::from_list(attr_args)?;
+        macro_args.resolve(ident);
+        let repr = reverse_args.repr.as_ref().unwrap_or(field_ty);
+        output.push(rustify_newtype(ident, &item_impls, repr, macro_args)?);
+    }
+    if output.is_empty() {
+        return Err(syn::Error::new(
+            item_mod.ident.span(),
+            "expected a `Type` alias or bindgen newtypes with constants in the module",
+        ));
+    }
+    for tokens in output {
+        items.extend(syn::parse2::<syn::File>(tokens)?.items);
+    }
+    Ok(Item::Mod(item_mod))
+}
+
+/// Whether `item_mod` is a bindgen `constified_enum_module`, with the `Type` of its constants.
+fn has_type_alias(item_mod: &ItemMod) -> bool {
+    item_mod.content.as_ref().is_some_and(|(_, items)| {
+        items.iter().any(|item| matches!(item, Item::Type(item_type) if item_type.ident == "Type"))
+    })
+}
+
+/// Derives a newtype and the rustified enum from a bindgen `constified_enum_module`, e.g.
+/// `mod Foo { pub type Type = u32; pub const A: Type = 0; }`.
+fn rustify_module(item_mod: &ItemMod, mut macro_args: MacroArgs, reverse_args: ReverseArgs) -> Result<TokenStream, syn::Error> {
+    let mod_ident = &item_mod.ident;
+    let Some((_, items)) = &item_mod.content else {
+        return Err(syn::Error::new(Span::call_site(), "`enum_ffi_rustify` can only be applied to inline modules"));
+    };
+    let mut type_alias = None;
+    let mut constants = vec![];
+    for item in items {
+        match item {
+            Item::Type(item_type) if item_type.ident == "Type" => type_alias = Some(&*item_type.ty),
+            Item::Const(item_const) if matches!(&*item_const.ty, Type::Path(type_path) if type_path.path.is_ident("Type")) => {
+                let const_ident = &item_const.ident;
+                let value = if is_literal(&item_const.expr) {
+                    (*item_const.expr).clone()
+                } else {
+                    parse_quote! { #mod_ident::#const_ident }
+                };
+                constants.push(Constant {
+                    attrs: item_const.attrs.clone(),
+                    vis: item_const.vis.clone(),
+                    ident: const_ident.clone(),
+                    value,
+                });
+            }
+            _ => {}
+        }
+    }
+    let int_type = match (&reverse_args.repr, type_alias) {
+        (Some(repr), _) => int_type(repr)?,
+        (None, Some(type_alias)) => int_type(type_alias)?,
+        (None, None) => return Err(syn::Error::new(mod_ident.span(), "expected a `Type` alias in the module")),
+    };
+
+    macro_args.resolve(mod_ident);
+    macro_args.rust_enum_name.get_or_insert_with(|| format!("{mod_ident}Rustified"));
+    if !macro_args.exhaustive {
+        macro_args.catch_all.get_or_insert_with(|| format!("UnknownVariant{mod_ident}"));
+    }
+    let newtype_ident = match &reverse_args.newtype_name {
+        Some(name) => format_ident!("{}", name.replace("{}", &mod_ident.to_string())),
+        None => format_ident!("{}Newtype", mod_ident),
+    };
+    let item_enum = synthesize_enum(&newtype_ident, item_mod.vis.clone(), int_type, constants)?;
+    expand(item_enum, macro_args, Newtype::Generated(quote! { #mod_ident::Type }), int_type)
+}
+
+pub(crate) fn enum_ffi_rustify(item: Item, mut attr_args: Vec<NestedMeta>) -> Result<TokenStream, syn::Error> {
+    let reverse_args = ReverseArgs::from_args(&mut attr_args)?;
+    let mut macro_args = MacroArgs::from_list(&attr_args)?;
+    let output = match &item {
+        Item::Impl(item_impl) => {
+            let ident = impl_newtype(item_impl)
+                .ok_or(syn::Error::new(item_impl.self_ty.span(), "expected an inherent impl of a newtype"))?;
+            let repr = reverse_args.repr.ok_or(syn::Error::new(
+                Span::call_site(),
+                "the type of the newtype field can't be seen from its constants, use `repr = \"...\"` to specify it",
+            ))?;
+            macro_args.resolve(ident);
+            rustify_newtype(ident, &[item_impl], &repr, macro_args)?
+        }
+        Item::Mod(item_mod) if has_type_alias(item_mod) => rustify_module(item_mod, macro_args, reverse_args)?,
+        Item::Mod(item_mod) => return rustify_newtypes(item_mod.clone(), &attr_args, reverse_args).map(ToTokens::into_token_stream),
+        _ => return Err(syn::Error::new(
+            item.span(),
+            "`enum_ffi_rustify` can only be applied to the `impl` block of a bindgen newtype or to a module of constants",
+        )),
+    };
+    Ok(quote! {
+        #item
+
+        #output
+    })
+}
@@ -0,0 +1,157 @@
+//! Rewriting generated bindings in build scripts.
+
+use std::path::Path;
+use std::{fmt, fs, io};
+use darling::ast::NestedMeta;
+use darling::FromMeta;
+use quote::ToTokens;
+use crate::module::{rewrite_items, Pattern, Selection};
+use crate::MacroArgs;
+
+/// Applies `#[enum_ffi]` to the enums of a generated Rust file, e.g. the bindings generated by
+/// bindgen in a build script.
+///
+/// The enums are converted exactly like the `#[enum_ffi]` attribute with the same arguments
+/// would, so the output is interchangeable with hand-annotated enums.
+///
+/// ```no_run
+/// # fn main() -> Result<(), enum_ffi_newtype_codegen::Error> {
+/// let bindings = std::path::Path::new(&std::env::var("OUT_DIR").unwrap()).join("bindings.rs");
+/// enum_ffi_newtype_codegen::Rewriter::new()
+///     .args(r#"rust_enum_name = "{}Rs""#)?
+///     .exclude_name("Internal")
+///     .rewrite_file(bindings)?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Rewriter {
+    args: Vec<NestedMeta>,
+    selection: Selection,
+}
+
+impl Rewriter {
+    /// A rewriter for all C-Style enums with a `repr` attribute, with the default arguments.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the arguments of `#[enum_ffi(...)]` used for all enums, e.g. `"exhaustive, display"`.
+    ///
+    /// In `rust_enum_name` and `catch_all`, `{}` is replaced by the name of the enum.
+    pub fn args(mut self, args: &str) -> Result<Self, Error> {
+        let args = NestedMeta::parse_meta_list(args.parse()?)?;
+        MacroArgs::from_list(&args).map_err(syn::Error::from)?;
+        self.args = args;
+        Ok(self)
+    }
+
+    /// Rewrites the enum with this name. Once an enum is included, all other enums are
+    /// left untouched unless they are included as well.
+    pub fn enum_name(mut self, name: &str) -> Self {
+        self.selection.include.get_or_insert_with(Vec::new).push(Pattern::Name(name.into()));
+        self
+    }
+
+    /// Rewrites the enums whose name matches `regex`, like [`Self::enum_name`].
+    #[cfg(feature = "regex")]
+    pub fn enum_regex(mut self, regex: &str) -> Result<Self, Error> {
+        let regex = regex::Regex::new(regex)?;
+        self.selection.include.get_or_insert_with(Vec::new).push(Pattern::Regex(regex));
+        Ok(self)
+    }
+
+    /// Leaves the enum with this name untouched.
+    pub fn exclude_name(mut self, name: &str) -> Self {
+        self.selection.exclude.push(Pattern::Name(name.into()));
+        self
+    }
+
+    /// Leaves the enums whose name matches `regex` untouched.
+    #[cfg(feature = "regex")]
+    pub fn exclude_regex(mut self, regex: &str) -> Result<Self, Error> {
+        self.selection.exclude.push(Pattern::Regex(regex::Regex::new(regex)?));
+        Ok(self)
+    }
+
+    /// Rewrites the enums in `file`, including those in inline modules.
+    pub fn rewrite(&self, file: &mut syn::File) -> Result<(), Error> {
+        rewrite_items(&mut file.items, &self.selection, &self.args)?;
+        Ok(())
+    }
+
+    /// Rewrites the enums in the Rust source code `source`.
+    pub fn rewrite_str(&self, source: &str) -> Result<String, Error> {
+        let mut file = syn::parse_file(source)?;
+        self.rewrite(&mut file)?;
+        Ok(file.into_token_stream().to_string())
+    }
+
+    /// Rewrites the enums in the Rust file at `path` in place.
+    pub fn rewrite_file(&self, path: impl AsRef<Path>) -> Result<(), Error> {
+        let path = path.as_ref();
+        let source = fs::read_to_string(path)?;
+        fs::write(path, self.rewrite_str(&source)?)?;
+        Ok(())
+    }
+}
+
+/// The error returned by [`Rewriter`].
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum Error {
+    /// Reading or writing a file failed
+    Io(io::Error),
+    /// The arguments or the rewritten code are invalid
+    Syntax(syn::Error),
+    /// A regular expression is invalid
+    #[cfg(feature = "regex")]
+    Regex(regex::Error),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Io(err) => err.fmt(f),
+            Error::Syntax(err) => err.fmt(f),
+            #[cfg(feature = "regex")]
+            Error::Regex(err) => err.fmt(f),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Io(err) => Some(err),
+            Error::Syntax(err) => Some(err),
+            #[cfg(feature = "regex")]
+            Error::Regex(err) => Some(err),
+        }
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(err: io::Error) -> Self {
+        Error::Io(err)
+    }
+}
+
+impl From<syn::Error> for Error {
+    fn from(err: syn::Error) -> Self {
+        Error::Syntax(err)
+    }
+}
+
+impl From<proc_macro2::LexError> for Error {
+    fn from(err: proc_macro2::LexError) -> Self {
+        Error::Syntax(err.into())
+    }
+}
+
+#[cfg(feature = "regex")]
+impl From<regex::Error> for Error {
+    fn from(err: regex::Error) -> Self {
+        Error::Regex(err)
+    }
+}
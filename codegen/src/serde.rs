@@ -0,0 +1,182 @@
+//! `Serialize` and `Deserialize` impls of the generated enums.
+
+use darling::FromMeta;
+use proc_macro2::{Span, TokenStream};
+use quote::{format_ident, quote, ToTokens};
+use syn::ext::IdentExt;
+use crate::discriminant::Discriminant;
+use crate::{CatchAll, FfiEnum, FfiVariant, Skip};
+
+/// How values are encoded by the generated serde impls.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum SerdeEncoding {
+    /// The name of the associated constant of the FFI enum
+    Name,
+    /// The raw value
+    Number,
+    /// The name of known values, and the raw value of unknown values
+    NameOrNumber,
+}
+
+impl FromMeta for SerdeEncoding {
+    fn from_word() -> darling::Result<Self> {
+        Ok(SerdeEncoding::NameOrNumber)
+    }
+
+    fn from_string(value: &str) -> darling::Result<Self> {
+        match value {
+            "name" => Ok(SerdeEncoding::Name),
+            "number" => Ok(SerdeEncoding::Number),
+            "name_or_number" => Ok(SerdeEncoding::NameOrNumber),
+            _ => Err(darling::Error::unknown_value(value)),
+        }
+    }
+}
+
+/// Generates `Serialize` and `Deserialize` for the FFI enum and the rustified enum.
+///
+/// Both enums share the encoding of the FFI enum, so that a value serialized as one of them
+/// can be deserialized as the other. Unknown values deserialize to the catch-all variant.
+pub(crate) fn serde_impls(ffi_enum: &FfiEnum, encoding: SerdeEncoding) -> Result<TokenStream, syn::Error> {
+    if !cfg!(feature = "serde") {
+        return Err(syn::Error::new(
+            Span::call_site(),
+            "`serde` requires the `serde` feature of `enum-ffi-newtype`",
+        ));
+    }
+    let FfiEnum { original_ident, rust_enum_ident, repr_tokens, base_repr_tokens, non_zero, variants, .. } = ffi_enum;
+    let visitor_ident = format_ident!("{}Visitor", original_ident);
+    let variant_idents: Vec<_> = variants.iter().map(|variant| &variant.ident).collect();
+    let variant_cfgs: Vec<_> = variants.iter().map(|variant| &variant.cfg).collect();
+    let rust_idents = variants.iter().map(|variant| &variant.rust_ident);
+    let variant_names = variants.iter().map(|variant| variant.ident.to_string());
+
+    let serialize = match encoding {
+        SerdeEncoding::Name => {
+            let unknown_error = format!("`{original_ident}` value {{}} has no name");
+            quote! {
+                match self.name() {
+                    Some(name) => serializer.serialize_str(name),
+                    None => Err(serde::ser::Error::custom(format_args!(#unknown_error, self.0))),
+                }
+            }
+        }
+        SerdeEncoding::Number => quote! { serde::Serialize::serialize(&self.0, serializer) },
+        SerdeEncoding::NameOrNumber => quote! {
+            match self.name() {
+                Some(name) => serializer.serialize_str(name),
+                None => serde::Serialize::serialize(&self.0, serializer),
+            }
+        },
+    };
+
+    let from_raw = if *non_zero {
+        quote! { .and_then(core::num::NonZero::new) }
+    } else {
+        quote! {}
+    };
+    let visit_numbers = [
+        (quote! { visit_u64 }, quote! { u64 }, quote! { Unsigned }),
+        (quote! { visit_i64 }, quote! { i64 }, quote! { Signed }),
+    ].into_iter().map(|(visit_fn, ty, unexpected)| quote! {
+        fn #visit_fn<E: serde::de::Error>(self, value: #ty) -> Result<#original_ident, E> {
+            #base_repr_tokens::try_from(value).ok()
+                #from_raw
+                .map(#original_ident)
+                .ok_or_else(|| E::invalid_value(serde::de::Unexpected::#unexpected(value), &self))
+        }
+    });
+    let visit_numbers = quote! { #(#visit_numbers)* };
+    let visit_str = quote! {
+        fn visit_str<E: serde::de::Error>(self, value: &str) -> Result<#original_ident, E> {
+            const NAMES: &[&str] = &[#( #variant_cfgs #variant_names ),*];
+            #original_ident::from_name(value).ok_or_else(|| E::unknown_variant(value, NAMES))
+        }
+    };
+    let visitor = |expecting: String, visit_fns: TokenStream, deserialize_fn: TokenStream| quote! {
+        struct #visitor_ident;
+
+        impl serde::de::Visitor<'_> for #visitor_ident {
+            type Value = #original_ident;
+
+            fn expecting(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                f.write_str(#expecting)
+            }
+
+            #visit_fns
+        }
+
+        deserializer.#deserialize_fn(#visitor_ident)
+    };
+    let deserialize = match encoding {
+        SerdeEncoding::Name => visitor(format!("a `{original_ident}` name"), visit_str, quote! { deserialize_str }),
+        SerdeEncoding::Number => quote! {
+            <#repr_tokens as serde::Deserialize>::deserialize(deserializer).map(#original_ident)
+        },
+        SerdeEncoding::NameOrNumber => visitor(
+            format!("a `{original_ident}` name or value"),
+            quote! { #visit_str #visit_numbers },
+            quote! { deserialize_any },
+        ),
+    };
+
+    let catch_all_arm = match ffi_enum.extra_catch_all() {
+        Some(CatchAll { ident, keep_value: true, .. }) => quote! {
+            #rust_enum_ident::#ident(raw) => #original_ident(*raw),
+        },
+        Some(CatchAll { ident, .. }) => {
+            let value = ffi_enum.ffi_value(quote! { #rust_enum_ident::#ident as #base_repr_tokens });
+            quote! { #rust_enum_ident::#ident => #value, }
+        }
+        None => quote! {},
+    };
+    let skipped_arms = ffi_enum.skipped.iter().map(|variant| {
+        let FfiVariant { rust_ident, cfg, skip, .. } = variant;
+        let value = match skip {
+            Some(Skip::Value(value)) => ffi_enum.ffi_value(Discriminant::Value(*value).into_token_stream()),
+            _ => {
+                let message = format!("`{rust_enum_ident}::{}` has no `{original_ident}` value", rust_ident.unraw());
+                quote! { return Err(serde::ser::Error::custom(#message)) }
+            }
+        };
+        quote! { #cfg #rust_enum_ident::#rust_ident => #value, }
+    });
+    let to_rust = if ffi_enum.catch_all.is_some() {
+        quote! { Ok(#rust_enum_ident::from(value)) }
+    } else {
+        quote! { #rust_enum_ident::try_from(value).map_err(serde::de::Error::custom) }
+    };
+
+    Ok(quote! {
+        impl serde::Serialize for #original_ident {
+            fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                #serialize
+            }
+        }
+
+        impl<'de> serde::Deserialize<'de> for #original_ident {
+            fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                #deserialize
+            }
+        }
+
+        #[allow(deprecated)]
+        impl serde::Serialize for #rust_enum_ident {
+            fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                let value = match self {
+                    #( #variant_cfgs #rust_enum_ident::#rust_idents => #original_ident::#variant_idents, )*
+                    #( #skipped_arms )*
+                    #catch_all_arm
+                };
+                serde::Serialize::serialize(&value, serializer)
+            }
+        }
+
+        impl<'de> serde::Deserialize<'de> for #rust_enum_ident {
+            fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                let value = <#original_ident as serde::Deserialize>::deserialize(deserializer)?;
+                #to_rust
+            }
+        }
+    })
+}
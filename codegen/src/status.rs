@@ -0,0 +1,66 @@
+//! C status enums, whose success variant is left out of the FFI enum.
+
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::parse::{Parse, ParseStream};
+use syn::{Expr, Token, Type};
+use crate::{FfiEnum, FfiVariant};
+
+/// Generates the conversions between raw statuses and `Result<(), Foo>`.
+pub(crate) fn status_impls(ffi_enum: &FfiEnum, success: &FfiVariant) -> TokenStream {
+    let FfiEnum { original_ident, vis, base_repr_tokens, .. } = ffi_enum;
+    let success_doc = format!("The raw value of `{}`, which is not an error.", success.ident);
+    let cbindgen_ignore = ffi_enum.cbindgen_ignore();
+
+    quote! {
+        #cbindgen_ignore
+        impl #original_ident {
+            #[doc = #success_doc]
+            #vis const SUCCESS: #base_repr_tokens = 0;
+
+            /// Converts a raw status to `Ok(())` for success, and to the error otherwise.
+            #vis const fn into_result(raw: #base_repr_tokens) -> Result<(), Self> {
+                match core::num::NonZero::new(raw) {
+                    None => Ok(()),
+                    Some(raw) => Err(Self(raw)),
+                }
+            }
+
+            /// Converts a result back to the raw status.
+            #vis const fn from_result(result: Result<(), Self>) -> #base_repr_tokens {
+                match result {
+                    Ok(()) => Self::SUCCESS,
+                    Err(err) => err.0.get(),
+                }
+            }
+        }
+    }
+}
+
+/// The input of `ffi_try!`, e.g. `ffi_try!(FooStatus, unsafe { foo_init() })`.
+struct FfiTry {
+    status: Type,
+    raw: Expr,
+}
+
+impl Parse for FfiTry {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let status = input.parse()?;
+        input.parse::<Token![,]>()?;
+        let raw = input.parse()?;
+        input.parse::<Option<Token![,]>>()?;
+        Ok(FfiTry { status, raw })
+    }
+}
+
+/// Expands `ffi_try!`, which returns early with the error of a raw status, converted with
+/// `From` like `?` does.
+pub(crate) fn ffi_try(input: TokenStream) -> Result<TokenStream, syn::Error> {
+    let FfiTry { status, raw } = syn::parse2(input)?;
+    Ok(quote! {
+        match <#status>::into_result(#raw) {
+            Ok(()) => {}
+            Err(err) => return Err(core::convert::From::from(err)),
+        }
+    })
+}
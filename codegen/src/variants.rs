@@ -0,0 +1,134 @@
+//! Tables of the known variants, for enumerating and inspecting them.
+
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use crate::FfiEnum;
+
+/// Generates the variant lists of both enums and the `VARIANTS` table of the FFI enum.
+///
+/// Items whose name is taken by a constant or variant, e.g. a variant `ALL`, are left out, and
+/// the other items don't refer to them.
+pub(crate) fn variant_tables(ffi_enum: &FfiEnum) -> TokenStream {
+    let FfiEnum { original_ident, rust_enum_ident, vis, repr_tokens, variants, .. } = ffi_enum;
+    let info_ident = format_ident!("{}VariantInfo", original_ident);
+    let info_doc = format!("The name, raw value and documentation of a known [`{original_ident}`] value.");
+    let variant_idents: Vec<_> = variants.iter().map(|variant| &variant.ident).collect();
+    let variant_cfgs: Vec<_> = variants.iter().map(|variant| &variant.cfg).collect();
+    // A declared catch-all variant has a raw value, but it stands for the unknown values.
+    let listed: Vec<_> = variants.iter()
+        .filter(|variant| ffi_enum.catch_all.as_ref().is_none_or(|catch_all| catch_all.ident != variant.rust_ident))
+        .collect();
+    let listed_cfgs = listed.iter().map(|variant| &variant.cfg);
+    let listed_idents = listed.iter().map(|variant| &variant.rust_ident);
+    let variant_names = variants.iter().map(|variant| variant.ident.to_string());
+    let variant_docs = variants.iter().map(|variant| &variant.doc);
+    let raw_index = ffi_enum.raw_value(quote! { KNOWN[index] });
+    let raw_known = ffi_enum.raw_value(quote! { KNOWN[known] });
+    let cbindgen_ignore = ffi_enum.cbindgen_ignore();
+
+    // Nested items can't refer to `Self`.
+    let known = quote! {
+        const KNOWN: &[#original_ident] = &[#( #variant_cfgs #original_ident(#original_ident::#variant_idents.0) ),*];
+    };
+    let all = quote! {
+        const ALL: &[#rust_enum_ident] = &[#( #listed_cfgs #rust_enum_ident::#listed_idents ),*];
+    };
+    let newtype_item = |name: &str, item: TokenStream| (!ffi_enum.has_constant(name)).then_some(item);
+    let rust_item = |name: &str, item: TokenStream| (!ffi_enum.has_rust_variant(name)).then_some(item);
+
+    let known_item = newtype_item("KNOWN", quote! {
+        /// All known values, in declaration order.
+        #vis const KNOWN: &'static [Self] = { #known KNOWN };
+    });
+    let count_item = newtype_item("VARIANT_COUNT", quote! {
+        /// The number of known values.
+        #vis const VARIANT_COUNT: usize = { #known KNOWN.len() };
+    });
+    let variants_item = newtype_item("VARIANTS", quote! {
+        /// The name, raw value and documentation of all known values, in declaration order.
+        #vis const VARIANTS: &'static [#info_ident] = &[#(
+            #variant_cfgs
+            #info_ident {
+                name: #variant_names,
+                value: #original_ident::#variant_idents.0,
+                doc: #variant_docs,
+            }
+        ),*];
+    });
+    let min_item = newtype_item("MIN_KNOWN", quote! {
+        /// The known value with the smallest raw value.
+        #vis const MIN_KNOWN: Self = {
+            #known
+            let mut index = 0;
+            let mut known = 1;
+            while known < KNOWN.len() {
+                if #raw_known < #raw_index {
+                    index = known;
+                }
+                known += 1;
+            }
+            Self(KNOWN[index].0)
+        };
+    });
+    let max_item = newtype_item("MAX_KNOWN", quote! {
+        /// The known value with the largest raw value.
+        #vis const MAX_KNOWN: Self = {
+            #known
+            let mut index = 0;
+            let mut known = 1;
+            while known < KNOWN.len() {
+                if #raw_known > #raw_index {
+                    index = known;
+                }
+                known += 1;
+            }
+            Self(KNOWN[index].0)
+        };
+    });
+    let all_item = rust_item("ALL", quote! {
+        /// All variants with a raw value of their own except the catch-all, in declaration order.
+        #vis const ALL: &'static [Self] = { #all ALL };
+    });
+    let rust_count_item = rust_item("VARIANT_COUNT", quote! {
+        /// The number of variants in [`Self::ALL`].
+        #vis const VARIANT_COUNT: usize = { #all ALL.len() };
+    });
+    let iter_item = rust_item("iter", quote! {
+        /// Iterates over the variants in [`Self::ALL`].
+        #vis fn iter() -> core::slice::Iter<'static, Self> {
+            #all
+            ALL.iter()
+        }
+    });
+
+    quote! {
+        #[doc = #info_doc]
+        #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+        #vis struct #info_ident {
+            /// The name of the associated constant
+            pub name: &'static str,
+            /// The raw value
+            pub value: #repr_tokens,
+            /// The doc comment of the variant, or an empty string
+            pub doc: &'static str,
+        }
+
+        #cbindgen_ignore
+        #[allow(deprecated)]
+        impl #original_ident {
+            #known_item
+            #count_item
+            #variants_item
+            #min_item
+            #max_item
+        }
+
+        #cbindgen_ignore
+        #[allow(deprecated)]
+        impl #rust_enum_ident {
+            #all_item
+            #rust_count_item
+            #iter_item
+        }
+    }
+}
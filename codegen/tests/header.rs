@@ -0,0 +1,120 @@
+use enum_ffi_newtype_codegen::Header;
+
+const SOURCE: &str = r#"
+/// The status of a foo
+#[enum_ffi(cbindgen(prefix = "FOO_"))]
+#[repr(u32)]
+pub enum Foo {
+    /// Nothing happened
+    A,
+    #[cfg(feature = "b")]
+    B = 0xffff_ffff,
+    #[enum_ffi(skip)]
+    Uninitialized = 7,
+}
+
+#[enum_ffi_newtype::enum_ffi(non_zero)]
+#[repr(i64)]
+pub enum FooError {
+    NotReady = -1,
+    Min = -9223372036854775808,
+}
+
+#[repr(u8)]
+pub enum NotFfi {
+    A,
+}
+
+#[enum_ffi_module(exclude(Excluded))]
+mod bindings {
+    #[repr(u8)]
+    pub enum Bar {
+        #[enum_ffi(alias = "Last", also = [4])]
+        X = 3,
+    }
+
+    #[repr(u8)]
+    pub enum Excluded {
+        X,
+    }
+}
+"#;
+
+#[test]
+fn test_c_header() {
+    let header = Header::new().source_str(SOURCE).unwrap().to_string();
+    let expected = r#"/* Generated by enum-ffi-newtype from the Rust definitions, do not edit. */
+
+#include <stdint.h>
+
+/**
+ * The status of a foo
+ */
+typedef uint32_t Foo;
+/**
+ * Nothing happened
+ */
+#define FOO_A ((Foo)0)
+/**
+ * Only available with `cfg(feature = "b")`.
+ */
+#define FOO_B ((Foo)4294967295LL)
+
+/**
+ * Never 0, so that 0 can stand for the absence of a value, e.g. for success.
+ */
+typedef int64_t FooError;
+#define FooError_NotReady ((FooError)-1)
+#define FooError_Min ((FooError)(-9223372036854775807LL - 1))
+
+typedef uint8_t Bar;
+#define Bar_X ((Bar)3)
+/**
+ * Alias of `Bar_X`.
+ */
+#define Bar_Last ((Bar)3)
+"#;
+    assert_eq!(expected, header);
+}
+
+#[test]
+fn test_cpp_enum_class() {
+    let header = Header::new()
+        .cpp_enum_class(true)
+        .source_str("#[enum_ffi] #[repr(i16)] enum Foo { A = -1, B }")
+        .unwrap()
+        .to_string();
+    assert!(header.ends_with(r#"
+#ifdef __cplusplus
+enum class Foo : int16_t {
+  A = -1,
+  B = 0,
+};
+#else
+typedef int16_t Foo;
+#define Foo_A ((Foo)-1)
+#define Foo_B ((Foo)0)
+#endif
+"#));
+}
+
+#[test]
+fn test_status() {
+    let header = Header::new()
+        .source_str("#[enum_ffi(status)] #[repr(i32)] enum FooStatus { Ok, Failed }")
+        .unwrap()
+        .to_string();
+    assert!(header.ends_with(r#"
+typedef int32_t FooStatus;
+#define FooStatus_Ok ((FooStatus)0)
+#define FooStatus_Failed ((FooStatus)1)
+"#));
+}
+
+#[test]
+fn test_errors() {
+    assert!(Header::new().source_str("#[enum_ffi] #[repr(u128)] enum Foo { A }").is_err());
+    assert!(Header::new().source_str("#[enum_ffi] #[repr(u8)] enum Foo { A = bindings::A }").is_err());
+    assert!(Header::new().source_str("#[enum_ffi(non_zero)] #[repr(u8)] enum Foo { A }").is_err());
+    assert!(Header::new().source_file("does/not/exist.rs").is_err());
+}
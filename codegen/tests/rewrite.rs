@@ -0,0 +1,108 @@
+use enum_ffi_newtype_codegen::{enum_ffi, Rewriter};
+use quote::quote;
+
+const BINDINGS: &str = r#"
+#![allow(non_camel_case_types)]
+
+#[repr(u32)]
+#[derive(Debug, Copy, Clone, Hash, PartialEq, Eq)]
+pub enum VkResult {
+    VK_SUCCESS = 0,
+    VK_NOT_READY = 1,
+}
+
+#[repr(u32)]
+#[derive(Debug, Copy, Clone, Hash, PartialEq, Eq)]
+pub enum GLenum {
+    GL_POINTS = 0,
+}
+
+pub mod nested {
+    #[repr(i32)]
+    pub enum VkFormat {
+        VK_FORMAT_UNDEFINED = 0,
+    }
+}
+
+pub struct NotAnEnum(pub u32);
+"#;
+
+#[test]
+fn test_matches_attribute() {
+    let rewritten = Rewriter::new()
+        .args(r#"rust_enum_name = "{}Rs", catch_all = "Unknown""#).unwrap()
+        .rewrite_str(BINDINGS)
+        .unwrap();
+    let expected = enum_ffi(
+        quote! { rust_enum_name = "VkResultRs", catch_all = "Unknown" },
+        quote! {
+            #[repr(u32)]
+            #[derive(Debug, Copy, Clone, Hash, PartialEq, Eq)]
+            pub enum VkResult {
+                VK_SUCCESS = 0,
+                VK_NOT_READY = 1,
+            }
+        },
+    ).unwrap();
+    assert!(rewritten.contains(&expected.to_string()));
+    assert!(rewritten.starts_with("# ! [allow (non_camel_case_types)]"));
+    assert!(rewritten.contains("pub enum GLenumRs"));
+    assert!(rewritten.contains("pub struct VkFormat"));
+    assert!(rewritten.contains("pub struct NotAnEnum"));
+}
+
+#[test]
+fn test_selection() {
+    let rewritten = Rewriter::new().enum_name("GLenum").rewrite_str(BINDINGS).unwrap();
+    assert!(rewritten.contains("pub struct GLenum"));
+    assert!(rewritten.contains("pub enum VkResult {"));
+}
+
+#[cfg(feature = "regex")]
+#[test]
+fn test_regex_selection() {
+    let rewritten = Rewriter::new().enum_regex("^Vk").unwrap().exclude_name("VkFormat").rewrite_str(BINDINGS).unwrap();
+    assert!(rewritten.contains("pub struct VkResult"));
+    assert!(rewritten.contains("pub enum GLenum {"));
+    assert!(rewritten.contains("pub enum VkFormat {"));
+    assert!(Rewriter::new().enum_regex("(").is_err());
+}
+
+#[test]
+fn test_cfg_attr_repr() {
+    let source = r#"
+        #[cfg_attr(windows, repr(i32))]
+        #[cfg_attr(not(windows), repr(u32))]
+        pub enum Foo {
+            A,
+        }
+
+        #[cfg_attr(windows, repr(i32))]
+        pub enum Bar {
+            A,
+        }
+    "#;
+    let rewritten = Rewriter::new().rewrite_str(source).unwrap();
+    assert!(rewritten.contains("# [cfg (windows)] # [repr (transparent)] # [derive"));
+    assert!(rewritten.contains("pub struct Foo (pub i32)"));
+    assert!(rewritten.contains("# [cfg (all (not (windows) , not (any (windows))))] # [repr (transparent)] # [derive"));
+    assert!(rewritten.contains("pub struct Foo (pub u32)"));
+    assert!(rewritten.contains(r#"# [cfg (not (any (windows)))] compile_error ! ("`Bar` has no `repr` for this target")"#));
+}
+
+#[test]
+fn test_errors() {
+    assert!(Rewriter::new().args("no_such_option").is_err());
+    assert!(Rewriter::new().args("non_zero").unwrap().rewrite_str(BINDINGS).is_err());
+    assert!(Rewriter::new().rewrite_str("enum {").is_err());
+}
+
+#[test]
+fn test_rewrite_file() {
+    let path = std::env::temp_dir().join(format!("enum-ffi-newtype-rewrite-{}.rs", std::process::id()));
+    std::fs::write(&path, BINDINGS).unwrap();
+    Rewriter::new().enum_name("GLenum").rewrite_file(&path).unwrap();
+    let rewritten = std::fs::read_to_string(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(Rewriter::new().enum_name("GLenum").rewrite_str(BINDINGS).unwrap(), rewritten);
+}
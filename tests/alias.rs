@@ -0,0 +1,165 @@
+// The aliases of deprecated variants must not use the deprecated constant themselves.
+#![deny(deprecated)]
+
+#[enum_ffi_newtype::enum_ffi(catch_all = "Unknown", keep_value, display, names)]
+#[repr(u8)]
+#[derive(Debug, PartialEq)]
+enum Foo {
+    VariantA,
+    /// The last variant
+    #[enum_ffi(alias = "VariantLast", also = [5, 6])]
+    VariantB,
+}
+
+/// Enough values that the conversion looks them up in a table
+#[enum_ffi_newtype::enum_ffi(names)]
+#[repr(i16)]
+#[derive(Debug, PartialEq)]
+enum FooTable {
+    #[enum_ffi(also = [-1, 1000])]
+    Variant0 = 0,
+    Variant1 = 10,
+    Variant2 = 20,
+    Variant3 = 30,
+    Variant4 = 40,
+    Variant5 = 50,
+    Variant6 = 60,
+    Variant7 = 70,
+    Variant8 = 80,
+    Variant9 = 90,
+    Variant10 = 100,
+    Variant11 = 110,
+    Variant12 = 120,
+    Variant13 = 130,
+    Variant14 = 140,
+    Variant15 = 150,
+    Variant16 = 160,
+    Variant17 = 170,
+    Variant18 = 180,
+    Variant19 = 190,
+    Variant20 = 200,
+    Variant21 = 210,
+    Variant22 = 220,
+    Variant23 = 230,
+    Variant24 = 240,
+    Variant25 = 250,
+    Variant26 = 260,
+    Variant27 = 270,
+    Variant28 = 280,
+    Variant29 = 290,
+    Variant30 = 300,
+    Variant31 = 310,
+    Variant32 = 320,
+    Variant33 = 330,
+    Variant34 = 340,
+    Variant35 = 350,
+    Variant36 = 360,
+    Variant37 = 370,
+    Variant38 = 380,
+    Variant39 = 390,
+    Variant40 = 400,
+    Variant41 = 410,
+    Variant42 = 420,
+    Variant43 = 430,
+    Variant44 = 440,
+    Variant45 = 450,
+    Variant46 = 460,
+    Variant47 = 470,
+    Variant48 = 480,
+    Variant49 = 490,
+    Variant50 = 500,
+    Variant51 = 510,
+    Variant52 = 520,
+    Variant53 = 530,
+    Variant54 = 540,
+    Variant55 = 550,
+    Variant56 = 560,
+    Variant57 = 570,
+    Variant58 = 580,
+    Variant59 = 590,
+    Variant60 = 600,
+    Variant61 = 610,
+    Variant62 = 620,
+    Variant63 = 630,
+    Variant64 = 640,
+}
+
+const BASE: u32 = 1;
+
+#[enum_ffi_newtype::enum_ffi(non_zero)]
+#[repr(u32)]
+#[derive(Debug, PartialEq)]
+enum FooGuards {
+    #[enum_ffi(also = [100])]
+    VariantA = BASE,
+    VariantB,
+}
+
+#[enum_ffi_newtype::enum_ffi]
+#[repr(u8)]
+#[derive(Debug, PartialEq)]
+enum FooCatchAll {
+    VariantA,
+    #[enum_ffi(also = [2])]
+    VariantB,
+}
+
+#[enum_ffi_newtype::enum_ffi(names)]
+#[repr(u8)]
+#[derive(Debug, PartialEq)]
+enum FooDeprecated {
+    VariantA,
+    #[deprecated = "use `VariantA` instead"]
+    #[enum_ffi(alias = "VariantOld")]
+    VariantB,
+}
+
+#[test]
+fn test_alias() {
+    assert_eq!(Foo::VariantB, Foo::VariantLast);
+    assert_eq!(Some(Foo::VariantB), Foo::from_name("VariantLast"));
+    assert_eq!(Some(FooRustified::VariantB), FooRustified::from_name("VariantLast"));
+    assert_eq!(Some("VariantB"), Foo::VariantLast.name());
+    assert_eq!(Some(FooDeprecated(1)), FooDeprecated::from_name("VariantOld"));
+}
+
+#[test]
+fn test_also() {
+    for raw in [1, 5, 6] {
+        assert_eq!(FooRustified::VariantB, Foo(raw).into());
+    }
+    assert_eq!(FooRustified::Unknown(7), Foo(7).into());
+    // Only the canonical value is produced.
+    assert_eq!(Foo::VariantB, FooRustified::VariantB.into());
+    assert_eq!(Foo(5), FooRustified::Unknown(5).into());
+    assert_eq!("VariantB", FooRustified::from(Foo(6)).to_string());
+
+    assert_eq!(Some(FooTableRustified::Variant0), FooTable(-1).try_to_rust());
+    assert_eq!(Some(FooTableRustified::Variant0), FooTable(1000).try_to_rust());
+    assert_eq!(Some(FooTableRustified::Variant64), FooTable(640).try_to_rust());
+    assert!(FooTable(1).try_to_rust().is_none());
+
+    assert_eq!(Some(FooGuardsRustified::VariantA), FooGuardsRustified::from_repr(100));
+    assert_eq!(Some(FooGuardsRustified::VariantB), FooGuardsRustified::from_repr(2));
+    assert_eq!(FooGuards::VariantA, FooGuardsRustified::VariantA.to_ffi());
+}
+
+#[test]
+fn test_also_catch_all_roundtrip() {
+    let unknown: FooCatchAll = FooCatchAllRustified::UnknownVariantFooCatchAll.into();
+    assert_ne!(FooCatchAll(2), unknown);
+    assert_eq!(FooCatchAllRustified::UnknownVariantFooCatchAll, unknown.into());
+    assert_eq!(FooCatchAllRustified::VariantB, FooCatchAll(2).into());
+}
+
+#[test]
+fn test_also_names() {
+    assert_eq!("Foo::VariantB", format!("{:?}", Foo(5)));
+    assert_eq!("VariantB", Foo(6).to_string());
+    assert_eq!(Some("VariantB"), Foo(5).name());
+    assert_eq!("Foo(7 /* unknown */)", format!("{:?}", Foo(7)));
+    assert_eq!(None, Foo(7).name());
+
+    assert_eq!("FooGuards::VariantA", format!("{:?}", FooGuards(core::num::NonZero::new(100).unwrap())));
+    assert_eq!(Some("Variant0"), FooTable(1000).name());
+}
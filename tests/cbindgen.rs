@@ -0,0 +1,32 @@
+#[enum_ffi_newtype::enum_ffi(cbindgen)]
+#[repr(u32)]
+#[derive(Debug, PartialEq)]
+pub enum Foo {
+    /// The first variant
+    VariantA,
+    #[enum_ffi(alias = "VariantLast")]
+    VariantB = 5,
+}
+
+#[enum_ffi_newtype::enum_ffi(cbindgen(prefix = "BAR_"))]
+#[repr(i8)]
+pub enum Bar {
+    A = -1,
+}
+
+#[enum_ffi_newtype::enum_ffi(non_zero, cbindgen(screaming_snake_case))]
+#[repr(i32)]
+pub enum FooError {
+    NotReady = 1,
+    TimedOut,
+}
+
+#[test]
+fn test_constants() {
+    assert_eq!(Foo::VariantA, Foo_VariantA);
+    assert_eq!(Foo::VariantB, Foo_VariantB);
+    assert_eq!(Foo::VariantB, Foo_VariantLast);
+    assert_eq!(Bar::A, BAR_A);
+    assert_eq!(FooError::TimedOut.0.get(), FOO_ERROR_TIMED_OUT);
+    assert_eq!(FooError::NotReady.0.get(), FOO_ERROR_NOT_READY);
+}
@@ -0,0 +1,45 @@
+#[enum_ffi_newtype::enum_ffi]
+#[repr(u32)]
+#[derive(Debug, PartialEq)]
+enum FooCfg {
+    Always,
+    #[cfg(not(test))]
+    Never,
+    AfterNever,
+    #[cfg(test)]
+    Enabled,
+    AfterEnabled,
+    #[cfg_attr(test, cfg(not(test)))]
+    NeverViaCfgAttr,
+    AfterNeverViaCfgAttr,
+}
+
+#[enum_ffi_newtype::enum_ffi(flags)]
+#[repr(u8)]
+#[derive(Debug, PartialEq)]
+enum FlagsCfg {
+    A = 1,
+    #[cfg(not(test))]
+    B = 2,
+    C = 4,
+}
+
+#[test]
+fn test_cfg_discriminants() {
+    assert_eq!(FooCfg::Always, FooCfg(0));
+    assert_eq!(FooCfg::AfterNever, FooCfg(1));
+    assert_eq!(FooCfg::Enabled, FooCfg(2));
+    assert_eq!(FooCfg::AfterEnabled, FooCfg(3));
+    assert_eq!(FooCfg::AfterNeverViaCfgAttr, FooCfg(4));
+    assert_eq!(FooCfgRustified::AfterNeverViaCfgAttr as u32, 4);
+    assert_eq!(FooCfgRustified::AfterNever, FooCfg(1).into());
+    assert_eq!(FooCfg::AfterEnabled, FooCfgRustified::AfterEnabled.into());
+}
+
+#[test]
+fn test_cfg_flags() {
+    assert_eq!(0b101, FlagsCfg::KNOWN_MASK);
+    let flags: Vec<_> = FlagsCfg(0b111).iter().collect();
+    assert_eq!(vec![FlagsCfgRustified::A, FlagsCfgRustified::C], flags);
+    assert_eq!(0b10, FlagsCfg(0b111).unknown_bits());
+}
@@ -0,0 +1,160 @@
+#[enum_ffi_newtype::enum_ffi]
+#[repr(u32)]
+#[derive(Debug, PartialEq)]
+enum FooDense {
+    Variant0 = 10,
+    Variant1 = 11,
+    Variant2 = 12,
+    Variant3 = 13,
+    Variant4 = 14,
+    Variant5 = 15,
+}
+
+#[enum_ffi_newtype::enum_ffi(catch_all = "Unknown", keep_value)]
+#[repr(i8)]
+#[derive(Debug, PartialEq)]
+enum FooSparse {
+    VariantA = -5,
+    VariantB = 3,
+    VariantC = 100,
+}
+
+/// More variants than a match is generated for, so they are looked up in a table
+#[enum_ffi_newtype::enum_ffi(variants)]
+#[repr(u16)]
+#[derive(Debug, PartialEq)]
+enum FooLarge {
+    Variant0 = 4096,
+    Variant1 = 4103,
+    Variant2 = 4110,
+    Variant3 = 4117,
+    Variant4 = 4124,
+    Variant5 = 4131,
+    Variant6 = 4138,
+    Variant7 = 4145,
+    Variant8 = 4152,
+    Variant9 = 4159,
+    Variant10 = 4166,
+    Variant11 = 4173,
+    Variant12 = 4180,
+    Variant13 = 4187,
+    Variant14 = 4194,
+    Variant15 = 4201,
+    Variant16 = 4208,
+    Variant17 = 4215,
+    Variant18 = 4222,
+    Variant19 = 4229,
+    Variant20 = 4236,
+    Variant21 = 4243,
+    Variant22 = 4250,
+    Variant23 = 4257,
+    Variant24 = 4264,
+    Variant25 = 4271,
+    Variant26 = 4278,
+    Variant27 = 4285,
+    Variant28 = 4292,
+    Variant29 = 4299,
+    Variant30 = 4306,
+    Variant31 = 4313,
+    Variant32 = 4320,
+    Variant33 = 4327,
+    Variant34 = 4334,
+    Variant35 = 4341,
+    Variant36 = 4348,
+    Variant37 = 4355,
+    Variant38 = 4362,
+    Variant39 = 4369,
+    Variant40 = 4376,
+    Variant41 = 4383,
+    Variant42 = 4390,
+    Variant43 = 4397,
+    Variant44 = 4404,
+    Variant45 = 4411,
+    Variant46 = 4418,
+    Variant47 = 4425,
+    Variant48 = 4432,
+    Variant49 = 4439,
+    Variant50 = 4446,
+    Variant51 = 4453,
+    Variant52 = 4460,
+    Variant53 = 4467,
+    Variant54 = 4474,
+    Variant55 = 4481,
+    Variant56 = 4488,
+    Variant57 = 4495,
+    Variant58 = 4502,
+    Variant59 = 4509,
+    Variant60 = 4516,
+    Variant61 = 4523,
+    Variant62 = 4530,
+    Variant63 = 4537,
+    Variant64 = 4544,
+    Variant65 = 4551,
+    Variant66 = 4558,
+    Variant67 = 4565,
+    Variant68 = 4572,
+    Variant69 = 4579,
+    Variant70 = 4586,
+    Variant71 = 4593,
+    Variant72 = 4600,
+    Variant73 = 4607,
+    Variant74 = 4614,
+    Variant75 = 4621,
+    Variant76 = 4628,
+    Variant77 = 4635,
+    Variant78 = 4642,
+    Variant79 = 4649,
+}
+
+#[test]
+fn test_dense() {
+    assert_eq!(Some(FooDenseRustified::Variant0), FooDense(10).try_to_rust());
+    assert_eq!(Some(FooDenseRustified::Variant5), FooDense(15).try_to_rust());
+    assert!(FooDense(9).try_to_rust().is_none());
+    assert!(FooDense(16).try_to_rust().is_none());
+    assert_eq!(FooDenseRustified::UnknownVariantFooDense, FooDense(u32::MAX).into());
+}
+
+#[test]
+fn test_sparse() {
+    assert_eq!(FooSparseRustified::VariantA, FooSparse(-5).into());
+    assert_eq!(FooSparseRustified::VariantC, FooSparse(100).into());
+    assert_eq!(FooSparseRustified::Unknown(4), FooSparse(4).into());
+}
+
+#[test]
+fn test_table() {
+    assert_eq!(Some(FooLargeRustified::Variant0), FooLarge(0x1000).try_to_rust());
+    assert_eq!(Some(FooLargeRustified::Variant42), FooLarge(0x1000 + 42 * 7).try_to_rust());
+    assert_eq!(Some(FooLargeRustified::Variant79), FooLarge(0x1000 + 79 * 7).try_to_rust());
+    assert_eq!(None, FooLarge(0x1001).try_to_rust());
+    assert_eq!(None, FooLarge(0).try_to_rust());
+    for (variant, rustified) in FooLarge::KNOWN.iter().zip(FooLargeRustified::ALL) {
+        assert_eq!(Some(rustified), variant.try_to_rust().as_ref());
+    }
+}
+
+const BASE: u8 = 7;
+
+#[enum_ffi_newtype::enum_ffi(non_zero)]
+#[repr(u8)]
+#[derive(Debug, PartialEq)]
+enum FooGuards {
+    VariantA = BASE,
+    VariantB,
+}
+
+#[test]
+fn test_const() {
+    const DENSE: [FooDenseRustified; 2] = [FooDense(11).to_rust(), FooDense(0).to_rust()];
+    assert_eq!([FooDenseRustified::Variant1, FooDenseRustified::UnknownVariantFooDense], DENSE);
+    const SPARSE: FooSparse = FooSparseRustified::Unknown(4).to_ffi();
+    assert_eq!(FooSparse(4), SPARSE);
+    const LARGE: Option<FooLargeRustified> = FooLargeRustified::from_repr(0x1000 + 7);
+    assert_eq!(Some(FooLargeRustified::Variant1), LARGE);
+    const GUARDS: [Option<FooGuardsRustified>; 3] =
+        [FooGuardsRustified::from_repr(8), FooGuardsRustified::from_repr(0), FooGuardsRustified::from_repr(9)];
+    assert_eq!([Some(FooGuardsRustified::VariantB), None, None], GUARDS);
+    assert_eq!(FooGuards::VariantA, FooGuardsRustified::VariantA.to_ffi());
+    assert_eq!(FooGuardsRustified::UnknownVariantFooGuards, FooGuards::from(FooGuardsRustified::UnknownVariantFooGuards).to_rust());
+}
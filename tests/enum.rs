@@ -36,3 +36,237 @@ fn test_roundtrip() {
     let back: Foo = foo.into();
     assert_eq!(Foo::Variant, back);
 }
+
+mod ffi {
+    pub const FOO_A: i32 = 40;
+}
+
+const BASE: u32 = 16;
+
+#[enum_ffi_newtype::enum_ffi]
+#[repr(u32)]
+#[derive(Debug, PartialEq)]
+enum FooExpr {
+    Shifted = 1 << 3,
+    Offset = BASE + 2,
+    AfterOffset,
+    Bindgen = ffi::FOO_A as u32,
+    AfterBindgen,
+    Alias = Self::Shifted as u32 + 100,
+    AliasBindgen = FooExpr::AfterBindgen as u32 * 2,
+}
+
+#[enum_ffi_newtype::enum_ffi]
+#[repr(i32)]
+#[derive(Debug, PartialEq)]
+enum FooSigned {
+    Negative = -1,
+    Zero,
+}
+
+#[test]
+fn test_expr_discriminants() {
+    assert_eq!(FooExpr::Shifted, FooExpr(8));
+    assert_eq!(FooExpr::Offset, FooExpr(18));
+    assert_eq!(FooExpr::AfterOffset, FooExpr(19));
+    assert_eq!(FooExpr::Bindgen, FooExpr(40));
+    assert_eq!(FooExpr::AfterBindgen, FooExpr(41));
+    assert_eq!(FooExpr::Alias, FooExpr(108));
+    assert_eq!(FooExpr::AliasBindgen, FooExpr(82));
+    assert_eq!(FooExprRustified::AliasBindgen, FooExpr(82).into());
+    assert_eq!(FooExpr::AfterBindgen, FooExprRustified::AfterBindgen.into());
+
+    assert_eq!(FooSigned::Negative, FooSigned(-1));
+    assert_eq!(FooSigned::Zero, FooSigned(0));
+    assert_eq!(FooSignedRustified::Negative, FooSigned(-1).into());
+}
+
+#[enum_ffi_newtype::enum_ffi]
+#[repr(u64)]
+#[derive(Debug, PartialEq)]
+enum FooWide {
+    Octal = 0o17,
+    Binary = 0b1010u64,
+    Byte = b'A' as u64,
+    Truncated = -1i8 as u8 as u64,
+    Max = 0xFFFF_FFFF_FFFF_FFFE,
+}
+
+#[enum_ffi_newtype::enum_ffi]
+#[repr(i8)]
+#[derive(Debug, PartialEq)]
+enum FooI8 {
+    Min = -128,
+    Inverted = !0,
+}
+
+#[enum_ffi_newtype::enum_ffi(names)]
+#[repr(u128)]
+#[derive(Debug, PartialEq)]
+enum FooU128 {
+    Small = 1,
+    High = 1 << 127,
+    Max = 0xFFFF_FFFF_FFFF_FFFF_FFFF_FFFF_FFFF_FFFE,
+}
+
+#[test]
+fn test_wide_discriminants() {
+    assert_eq!(FooWide::Max, FooWide(u64::MAX - 1));
+    assert_eq!(FooWideRustified::UnknownVariantFooWide as u64, u64::MAX);
+    assert_eq!(FooWide::Octal, FooWide(15));
+    assert_eq!(FooWide::Binary, FooWide(10));
+    assert_eq!(FooWide::Byte, FooWide(65));
+    assert_eq!(FooWide::Truncated, FooWide(255));
+    assert_eq!(FooI8::Min, FooI8(i8::MIN));
+    assert_eq!(FooI8::Inverted, FooI8(-1));
+
+    assert_eq!(FooU128::High, FooU128(1 << 127));
+    assert_eq!(FooU128::Max, FooU128(u128::MAX - 1));
+    assert_eq!(FooU128Rustified::Max, FooU128(u128::MAX - 1).into());
+    assert_eq!(FooU128Rustified::UnknownVariantFooU128, FooU128(2).into());
+    assert_eq!(FooU128Rustified::UnknownVariantFooU128 as u128, u128::MAX);
+    assert_eq!(Some("High"), FooU128(1 << 127).name());
+}
+
+#[test]
+fn test_catch_all() {
+    assert_eq!(FooWithCatchAllRustified::Variant2, FooWithCatchAll(1).into());
+    assert_eq!(FooWithCatchAllRustified::Unknown, FooWithCatchAll(17).into());
+}
+
+#[enum_ffi_newtype::enum_ffi(catch_all = "Unknown", keep_value)]
+#[repr(u32)]
+#[derive(Debug, PartialEq)]
+enum FooKeepValue {
+    Variant,
+    Variant2,
+    Unknown = 5,
+}
+
+#[enum_ffi_newtype::enum_ffi(non_zero, keep_value)]
+#[repr(u32)]
+#[derive(Debug, PartialEq)]
+enum FooNonZeroKeepValue {
+    Variant = 1,
+    Variant2,
+}
+
+#[test]
+fn test_keep_value() {
+    assert_eq!(FooKeepValueRustified::Variant2, FooKeepValue(1).into());
+    assert_eq!(FooKeepValueRustified::Unknown(17), FooKeepValue(17).into());
+    assert_eq!(FooKeepValueRustified::Unknown(5), FooKeepValue::Unknown.into());
+    for raw in [0, 1, 5, 17, u32::MAX] {
+        let rust: FooKeepValueRustified = FooKeepValue(raw).into();
+        assert_eq!(FooKeepValue(raw), rust.into());
+    }
+
+    let unknown = core::num::NonZero::new(42).unwrap();
+    let rust: FooNonZeroKeepValueRustified = FooNonZeroKeepValue(unknown).into();
+    assert_eq!(FooNonZeroKeepValueRustified::UnknownVariantFooNonZeroKeepValue(unknown), rust);
+    assert_eq!(FooNonZeroKeepValue(unknown), rust.into());
+}
+
+#[test]
+fn test_non_zero_catch_all() {
+    let unknown: FooNonZero = FooNonZeroRustified::UnknownVariantFooNonZero.into();
+    assert_eq!(unknown.0.get(), 4);
+}
+
+#[enum_ffi_newtype::enum_ffi(non_zero)]
+#[repr(i32)]
+#[derive(Debug, PartialEq)]
+enum FooNonZeroNegative {
+    NotFound = -2,
+    Invalid = -1,
+}
+
+#[enum_ffi_newtype::enum_ffi(non_zero)]
+#[repr(u8)]
+#[derive(Debug, PartialEq)]
+enum FooNonZeroFull {
+    Variant = 255,
+}
+
+#[test]
+fn test_non_zero_catch_all_skips_zero() {
+    let unknown: FooNonZeroNegative = FooNonZeroNegativeRustified::UnknownVariantFooNonZeroNegative.into();
+    assert_ne!(unknown.0.get(), 0);
+    assert_eq!(FooNonZeroNegativeRustified::UnknownVariantFooNonZeroNegative, unknown.into());
+
+    let unknown: FooNonZeroFull = FooNonZeroFullRustified::UnknownVariantFooNonZeroFull.into();
+    assert_eq!(unknown.0.get(), 1);
+    assert_eq!(FooNonZeroFullRustified::UnknownVariantFooNonZeroFull, unknown.into());
+}
+
+#[enum_ffi_newtype::enum_ffi(exhaustive)]
+#[repr(u32)]
+#[derive(Debug, PartialEq)]
+enum FooExhaustive {
+    Variant,
+    Variant2,
+}
+
+#[test]
+fn test_try_from() {
+    assert_eq!(Some(FooRs::Variant3), Foo::Variant3.try_to_rust());
+    assert_eq!(None, Foo(17).try_to_rust());
+
+    assert_eq!(Ok(FooExhaustiveRustified::Variant2), FooExhaustive(1).try_into());
+    let err = FooExhaustiveRustified::try_from(FooExhaustive(2)).unwrap_err();
+    assert_eq!(TryFromFooExhaustiveError(2), err);
+    assert_eq!("unknown `FooExhaustive` value: 2", err.to_string());
+    let _: &dyn std::error::Error = &err;
+    assert_eq!(FooExhaustive::Variant, FooExhaustiveRustified::Variant.into());
+}
+
+#[enum_ffi_newtype::enum_ffi(newtype_derive(Debug), newtype_attr(must_use))]
+#[repr(u32)]
+#[derive(Debug, PartialEq)]
+enum FooMinimalDerives {
+    Variant,
+    Variant2,
+}
+
+#[enum_ffi_newtype::enum_ffi(inherit_derives)]
+#[repr(u32)]
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+enum FooInherited {
+    Variant,
+    #[default]
+    Variant2,
+}
+
+#[test]
+fn test_newtype_derives() {
+    let rust: FooMinimalDerivesRustified = FooMinimalDerives::Variant2.into();
+    assert_eq!(FooMinimalDerivesRustified::Variant2, rust);
+    let ffi: FooMinimalDerives = rust.into();
+    assert_eq!(1, ffi.0);
+
+    let default = FooInherited::default();
+    assert_eq!(FooInherited::Variant2, default);
+    assert_eq!(FooInheritedRustified::default(), default.into());
+}
+
+/// Enum with documented variants.
+#[enum_ffi_newtype::enum_ffi]
+#[repr(u32)]
+#[derive(Debug, PartialEq)]
+enum FooDocumented {
+    /// The current variant.
+    Current,
+    /// A variant which should no longer be used.
+    #[deprecated]
+    Old,
+    #[doc(hidden)]
+    Hidden = 0x20,
+}
+
+#[test]
+#[allow(deprecated)]
+fn test_deprecated_variant() {
+    assert_eq!(FooDocumentedRustified::Old, FooDocumented::Old.into());
+    assert_eq!(FooDocumented::Hidden, FooDocumentedRustified::Hidden.into());
+    assert_eq!(FooDocumented::Current, FooDocumentedRustified::Current.into());
+}
@@ -0,0 +1,59 @@
+#[enum_ffi_newtype::enum_ffi(flags)]
+#[repr(u32)]
+#[derive(Debug, PartialEq)]
+enum Flags {
+    None = 0,
+    Read = 1 << 0,
+    Write = 1 << 1,
+    Exec = 1 << 2,
+    ReadWrite = Self::Read as u32 | Self::Write as u32,
+}
+
+/// A flags enum with a constant `ALL` of its own
+#[enum_ffi_newtype::enum_ffi(flags)]
+#[repr(u8)]
+#[derive(Debug, PartialEq)]
+#[allow(non_camel_case_types, clippy::upper_case_acronyms)]
+enum FlagsAll {
+    A = 1,
+    B = 2,
+    ALL = 3,
+    KNOWN_MASK = 4,
+}
+
+#[test]
+fn test_bit_ops() {
+    let mut flags = Flags::Read | Flags::Exec;
+    assert_eq!(Flags(0b101), flags);
+    assert!(flags.contains(Flags::Read));
+    assert!(!flags.contains(Flags::ReadWrite));
+    flags.insert(Flags::Write);
+    assert!(flags.contains(Flags::ReadWrite));
+    flags.remove(Flags::Read);
+    assert_eq!(Flags::Write | Flags::Exec, flags);
+    flags &= Flags::Write;
+    assert_eq!(Flags::Write, flags);
+    flags ^= Flags::Write;
+    assert!(flags.is_empty());
+    assert_eq!(Flags::None, flags);
+
+    assert_eq!(0b111, Flags::KNOWN_MASK);
+    assert_eq!(Flags::ALL, !Flags::None);
+    assert_eq!(Flags::Exec, !(Flags::ReadWrite | Flags(0b1000)));
+}
+
+#[test]
+fn test_iter() {
+    let flags = Flags::ReadWrite | Flags::Exec | Flags(0b1_0000);
+    let set: Vec<FlagsRustified> = flags.iter().collect();
+    assert_eq!(vec![FlagsRustified::Read, FlagsRustified::Write, FlagsRustified::Exec], set);
+    assert_eq!(0b1_0000, flags.unknown_bits());
+    assert_eq!(0, Flags::None.into_iter().count());
+}
+
+#[test]
+fn test_name_clash() {
+    assert_eq!(FlagsAll(3), FlagsAll::ALL);
+    assert_eq!(FlagsAll(0b111), !FlagsAll(0));
+    assert_eq!(0b1000, FlagsAll(0b1001).unknown_bits());
+}
@@ -0,0 +1,57 @@
+#[enum_ffi_newtype::enum_ffi(display)]
+#[repr(u32)]
+#[derive(Debug, PartialEq)]
+enum Foo {
+    Variant,
+    #[enum_ffi(display = "second variant")]
+    Variant2,
+}
+
+#[enum_ffi_newtype::enum_ffi(display = "c_name", catch_all = "Unknown", keep_value)]
+#[repr(i32)]
+#[derive(Debug, PartialEq)]
+#[allow(non_camel_case_types)]
+enum FooKeepValue {
+    FOO_A,
+    FOO_B,
+}
+
+#[enum_ffi_newtype::enum_ffi(flags)]
+#[repr(u8)]
+#[derive(Debug, PartialEq)]
+enum Flags {
+    Read = 1 << 0,
+    Write = 1 << 1,
+    ReadWrite = Self::Read as u8 | Self::Write as u8,
+    Exec = 1 << 2,
+}
+
+#[test]
+fn test_debug() {
+    assert_eq!("Foo::Variant2", format!("{:?}", Foo::Variant2));
+    assert_eq!("Foo(17 /* unknown */)", format!("{:?}", Foo(17)));
+    assert_eq!("Flags::ReadWrite", format!("{:?}", Flags::ReadWrite));
+    assert_eq!("Flags::Read | Flags::Exec", format!("{:?}", Flags::Read | Flags::Exec));
+    assert_eq!("Flags::Write | Flags(0x10 /* unknown */)", format!("{:?}", Flags::Write | Flags(0x10)));
+    assert_eq!("Flags(0x0 /* unknown */)", format!("{:?}", Flags(0)));
+}
+
+#[test]
+fn test_display() {
+    assert_eq!("Variant", Foo::Variant.to_string());
+    assert_eq!("second variant", Foo::Variant2.to_string());
+    assert_eq!("17", Foo(17).to_string());
+    assert_eq!("second variant", FooRustified::Variant2.to_string());
+    assert_eq!("UnknownVariantFoo", FooRustified::UnknownVariantFoo.to_string());
+
+    assert_eq!("FOO_B", FooKeepValue::FOO_B.to_string());
+    assert_eq!("FOO_A", FooKeepValueRustified::FOO_A.to_string());
+    assert_eq!("-3", FooKeepValueRustified::Unknown(-3).to_string());
+}
+
+#[test]
+fn test_radix() {
+    assert_eq!("0x11", format!("{:#x}", Foo(17)));
+    assert_eq!("0b110", format!("{:#b}", Flags::Write | Flags::Exec));
+    assert_eq!("1F", format!("{:X}", Foo(31)));
+}
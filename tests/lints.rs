@@ -0,0 +1,20 @@
+// Lint attributes of the variants must not cause warnings on the generated items.
+#![deny(warnings)]
+
+#[enum_ffi_newtype::enum_ffi]
+#[repr(u32)]
+#[derive(Debug, PartialEq)]
+enum Foo {
+    FooA,
+    #[expect(non_camel_case_types)]
+    #[enum_ffi(alias = "FOO_LAST")]
+    FOO_B,
+    #[cfg_attr(all(), expect(non_camel_case_types))]
+    FOO_C,
+}
+
+#[test]
+fn test_expect() {
+    assert_eq!(FooRustified::FOO_B, Foo::FOO_LAST.into());
+    assert_eq!(Foo(2), FooRustified::FOO_C.into());
+}
@@ -0,0 +1,64 @@
+#[enum_ffi_newtype::enum_ffi_module(rust_enum_name = "{}Rs", catch_all = "Unknown{}", exclude(Excluded))]
+mod bindings {
+    #[repr(u32)]
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    pub enum Foo {
+        VariantA,
+        VariantB,
+    }
+
+    #[repr(i32)]
+    #[derive(Debug, PartialEq)]
+    #[enum_ffi(catch_all = "Other", keep_value)]
+    pub enum Bar {
+        VariantA = -1,
+        VariantB = 3,
+    }
+
+    #[repr(u8)]
+    #[derive(Debug, PartialEq)]
+    pub enum Excluded {
+        VariantA,
+    }
+
+    #[derive(Debug, PartialEq)]
+    pub enum NotRepr {
+        VariantA,
+    }
+}
+
+#[enum_ffi_newtype::enum_ffi_module(include(Included), exhaustive)]
+mod selected {
+    #[repr(u8)]
+    #[derive(Debug, PartialEq)]
+    pub enum Included {
+        VariantA,
+    }
+
+    #[repr(u8)]
+    pub enum Other {
+        VariantA,
+    }
+}
+
+#[test]
+fn test_module() {
+    use bindings::*;
+
+    assert_eq!(FooRs::VariantB, Foo::VariantB.into());
+    assert_eq!(FooRs::UnknownFoo, Foo(5).into());
+    assert_eq!(BarRs::Other(7), Bar(7).into());
+    assert_eq!(Bar::VariantA, BarRs::VariantA.into());
+
+    // Enums which are left out keep their variants.
+    assert_eq!(0, Excluded::VariantA as u8);
+    assert_eq!(NotRepr::VariantA, NotRepr::VariantA);
+}
+
+#[test]
+fn test_include() {
+    use selected::*;
+
+    assert_eq!(Ok(IncludedRustified::VariantA), IncludedRustified::try_from(Included(0)));
+    assert_eq!(0, Other::VariantA as u8);
+}
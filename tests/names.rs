@@ -0,0 +1,53 @@
+use std::str::FromStr;
+
+#[enum_ffi_newtype::enum_ffi(names)]
+#[repr(u32)]
+#[derive(Debug, PartialEq)]
+enum Foo {
+    VariantA,
+    VariantB = 5,
+}
+
+#[enum_ffi_newtype::enum_ffi(non_zero, name_case_insensitive, name_aliases, catch_all = "Unknown", keep_value)]
+#[repr(i32)]
+#[derive(Debug, PartialEq)]
+enum FooAliases {
+    FirstError = 1,
+    SecondError,
+}
+
+#[test]
+fn test_name() {
+    assert_eq!(Some("VariantB"), Foo::VariantB.name());
+    assert_eq!(None, Foo(17).name());
+    assert_eq!("VariantA", FooRustified::VariantA.name());
+    assert_eq!("UnknownVariantFoo", FooRustified::UnknownVariantFoo.name());
+    assert_eq!(Some("SecondError"), FooAliases::SecondError.name());
+    assert_eq!("Unknown", FooAliasesRustified::Unknown(core::num::NonZero::new(7).unwrap()).name());
+
+    const NAME: Option<&str> = Foo::VariantA.name();
+    assert_eq!(Some("VariantA"), NAME);
+}
+
+#[test]
+fn test_from_name() {
+    assert_eq!(Some(Foo::VariantB), Foo::from_name("VariantB"));
+    assert_eq!(None, Foo::from_name("variantb"));
+    assert_eq!(None, Foo::from_name("UnknownVariantFoo"));
+    assert_eq!(Some(FooRustified::VariantA), FooRustified::from_name("VariantA"));
+    assert_eq!(None, FooRustified::from_name("UnknownVariantFoo"));
+
+    assert_eq!(Some(FooAliases::SecondError), FooAliases::from_name("seconderror"));
+    assert_eq!(Some(FooAliases::FirstError), FooAliases::from_name("FIRST_ERROR"));
+    assert_eq!(Some(FooAliasesRustified::FirstError), FooAliasesRustified::from_name("first_error"));
+    assert_eq!(None, FooAliasesRustified::from_name("Unknown"));
+}
+
+#[test]
+fn test_from_str() {
+    assert_eq!(Ok(Foo::VariantA), "VariantA".parse());
+    assert_eq!(Ok(FooRustified::VariantB), FooRustified::from_str("VariantB"));
+    let err = Foo::from_str("VariantC").unwrap_err();
+    assert_eq!(ParseFooError("VariantC".into()), err);
+    assert_eq!("unknown `Foo` name: \"VariantC\"", err.to_string());
+}
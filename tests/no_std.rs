@@ -0,0 +1,17 @@
+//! The generated code only uses `core`, so that it works in `no_std` crates.
+#![no_std]
+
+#[enum_ffi_newtype::enum_ffi]
+#[repr(u32)]
+#[derive(Debug, PartialEq)]
+pub enum Foo {
+    A,
+    B,
+    C,
+}
+
+#[test]
+fn test_no_std() {
+    assert_eq!(FooRustified::B, Foo(1).into());
+    assert_eq!(Foo::C, FooRustified::C.into());
+}
@@ -0,0 +1,62 @@
+mod ffi {
+    #[enum_ffi_newtype::enum_ffi(private_field)]
+    #[repr(u32)]
+    #[derive(Debug, PartialEq)]
+    pub enum Foo {
+        VariantA,
+        #[enum_ffi(also = [6])]
+        VariantB = 5,
+    }
+
+    #[enum_ffi_newtype::enum_ffi(private_field, non_zero)]
+    #[repr(i32)]
+    #[derive(Debug, PartialEq)]
+    pub enum FooError {
+        First = 1,
+        Second,
+    }
+
+    #[enum_ffi_newtype::enum_ffi(private_field, flags, display, catch_all = "Other", keep_value)]
+    #[repr(u8)]
+    pub enum Flags {
+        A = 1,
+        B = 2,
+    }
+}
+
+use core::num::NonZero;
+use ffi::*;
+
+#[test]
+fn test_raw() {
+    const B: Foo = Foo::from_raw(5);
+    assert_eq!(Foo::VariantB, B);
+    assert_eq!(5, Foo::VariantB.into_raw());
+    assert_eq!(&5, Foo::VariantB.as_raw());
+    assert_eq!(FooRustified::UnknownVariantFoo, Foo::from_raw(17).into());
+
+    assert_eq!(Some(Foo::VariantA), Foo::checked(0));
+    assert_eq!(None, Foo::checked(17));
+    assert_eq!(Some(FooRustified::VariantB), Foo::checked(6).map(Foo::to_rust));
+
+    assert_eq!(FooError::Second, FooError::from_raw(NonZero::new(2).unwrap()));
+    assert_eq!(Some(FooError::First), FooError::checked(1));
+    assert_eq!(None, FooError::checked(0));
+    // SAFETY: 7 is not zero.
+    assert_eq!(7, unsafe { FooError::from_raw_unchecked(7) }.into_raw().get());
+}
+
+#[test]
+fn test_flags() {
+    assert_eq!(3, (Flags::A | Flags::B).into_raw());
+    assert_eq!(None, Flags::checked(3));
+}
+
+#[test]
+fn test_match() {
+    match Foo::from_raw(5) {
+        Foo::VariantA => panic!(),
+        Foo::VariantB => {}
+        _ => panic!(),
+    }
+}
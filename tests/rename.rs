@@ -0,0 +1,83 @@
+#[enum_ffi_newtype::enum_ffi(strip_prefix = "FOO_STATUS_", rename_all = "PascalCase", display = "rust_name", names)]
+#[repr(u32)]
+#[derive(Debug, PartialEq)]
+#[allow(non_camel_case_types)]
+enum FooStatus {
+    FOO_STATUS_NOT_READY = 1,
+    FOO_STATUS_TIMED_OUT,
+    #[enum_ffi(rename = "Gone")]
+    FOO_STATUS_DISCONNECTED,
+    OTHER_PREFIX,
+}
+
+#[enum_ffi_newtype::enum_ffi(strip_prefix = "{}_", rename_all = "snake_case", display = "c_name", name_aliases)]
+#[repr(u8)]
+#[derive(Debug, PartialEq)]
+#[allow(non_camel_case_types)]
+enum Bar {
+    Bar_Type,
+    Bar_Value,
+}
+
+#[enum_ffi_newtype::enum_ffi(catch_all = "Unknown", strip_prefix = "BAZ_", rename_all = "PascalCase")]
+#[repr(i8)]
+#[derive(Debug, PartialEq)]
+#[allow(non_camel_case_types)]
+enum Baz {
+    BAZ_A,
+    BAZ_UNKNOWN = -1,
+}
+
+#[enum_ffi_newtype::enum_ffi(strip_prefix = "QUX_", rename_all = "PascalCase", const_strip_prefix = "QUX_", const_rename_all = "SCREAMING_SNAKE_CASE", names, inherit_derives)]
+#[repr(u16)]
+#[derive(Debug, PartialEq, Default)]
+#[allow(non_camel_case_types)]
+enum Qux {
+    #[default]
+    QUX_first_item,
+    #[enum_ffi(rename = "Later", const_rename = "SECOND")]
+    QUX_second_item,
+}
+
+#[test]
+fn test_names() {
+    assert_eq!(FooStatusRustified::NotReady, FooStatus::FOO_STATUS_NOT_READY.into());
+    assert_eq!(FooStatusRustified::TimedOut, FooStatus(2).into());
+    assert_eq!(FooStatus::FOO_STATUS_DISCONNECTED, FooStatusRustified::Gone.into());
+    assert_eq!(FooStatusRustified::OtherPrefix, FooStatus::OTHER_PREFIX.into());
+    assert_eq!(Some("FOO_STATUS_TIMED_OUT"), FooStatus(2).name());
+    assert_eq!("TimedOut", FooStatusRustified::TimedOut.name());
+    assert_eq!(Some(FooStatusRustified::Gone), FooStatusRustified::from_name("Gone"));
+    assert_eq!(None, FooStatusRustified::from_name("FOO_STATUS_DISCONNECTED"));
+    assert_eq!(Some(FooStatus::FOO_STATUS_DISCONNECTED), FooStatus::from_name("FOO_STATUS_DISCONNECTED"));
+
+    assert_eq!(BarRustified::r#type, Bar::Bar_Type.into());
+    assert_eq!("type", BarRustified::r#type.name());
+    assert_eq!(Some(BarRustified::value), "Bar_Value".parse().ok());
+    assert_eq!(Some(Bar::Bar_Value), "value".parse().ok());
+}
+
+#[test]
+fn test_const_rename() {
+    assert_eq!(QuxRustified::FirstItem, Qux::FIRST_ITEM.into());
+    assert_eq!(Qux::SECOND, QuxRustified::Later.into());
+    assert_eq!(Qux(1), Qux::SECOND);
+    assert_eq!(Qux::FIRST_ITEM, Qux::default());
+    assert_eq!(Some("SECOND"), Qux(1).name());
+    assert_eq!("Later", QuxRustified::Later.name());
+}
+
+#[test]
+fn test_display() {
+    assert_eq!("NotReady", FooStatusRustified::NotReady.to_string());
+    assert_eq!("NotReady", FooStatus::FOO_STATUS_NOT_READY.to_string());
+    assert_eq!("Bar_Type", BarRustified::r#type.to_string());
+}
+
+#[test]
+fn test_catch_all() {
+    assert_eq!(BazRustified::Unknown, Baz(-1).into());
+    assert_eq!(BazRustified::Unknown, Baz(5).into());
+    assert_eq!(Baz::BAZ_UNKNOWN, BazRustified::Unknown.into());
+}
+
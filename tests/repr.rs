@@ -0,0 +1,75 @@
+use core::any::TypeId;
+use core::ffi::{c_int, c_uint};
+
+#[enum_ffi_newtype::enum_ffi]
+#[repr(C)]
+#[derive(Debug, PartialEq)]
+enum FooC {
+    A = -1,
+    B,
+}
+
+#[enum_ffi_newtype::enum_ffi]
+#[repr(C)]
+enum FooCUnsigned {
+    A = 0x8000_0000,
+}
+
+#[enum_ffi_newtype::enum_ffi]
+#[repr(C, u8)]
+#[derive(Debug, PartialEq)]
+enum FooCU8 {
+    A = 200,
+}
+
+#[enum_ffi_newtype::enum_ffi]
+#[cfg_attr(test, repr(i16))]
+#[cfg_attr(not(test), repr(u64))]
+#[derive(Debug, PartialEq)]
+enum FooTarget {
+    A = 1,
+}
+
+#[enum_ffi_newtype::enum_ffi]
+#[repr(u8)]
+#[cfg_attr(all(test, not(test)), repr(u16))]
+#[derive(Debug, PartialEq)]
+enum FooFallback {
+    A = 1,
+}
+
+// The compiler resolves `cfg_attr` before `enum_ffi` sees the enum, but not inside a module.
+#[enum_ffi_newtype::enum_ffi_module]
+mod overlapping {
+    #[repr(u8)]
+    #[cfg_attr(test, repr(i32))]
+    #[cfg_attr(any(test, unix), repr(u16))]
+    #[derive(Debug, PartialEq)]
+    pub enum FooOverlapping {
+        A = 1,
+    }
+}
+use overlapping::*;
+
+fn field_type<T: 'static, F: 'static>(_: fn(F) -> T) -> TypeId {
+    TypeId::of::<F>()
+}
+
+#[test]
+fn test_repr_c() {
+    assert_eq!(TypeId::of::<c_int>(), field_type(FooC));
+    assert_eq!(FooCRustified::B, FooC(0).into());
+    assert_eq!(TypeId::of::<c_uint>(), field_type(FooCUnsigned));
+    assert_eq!(TypeId::of::<u8>(), field_type(FooCU8));
+    assert_eq!(FooCU8Rustified::A, FooCU8(200).into());
+}
+
+#[test]
+fn test_cfg_attr_repr() {
+    assert_eq!(TypeId::of::<i16>(), field_type(FooTarget));
+    assert_eq!(FooTargetRustified::A, FooTarget(1).into());
+    assert_eq!(TypeId::of::<u8>(), field_type(FooFallback));
+    assert_eq!(FooFallbackRustified::A, FooFallback::A.into());
+    assert_eq!(TypeId::of::<i32>(), field_type(FooOverlapping));
+    assert_eq!(FooOverlappingRustified::A, FooOverlapping(1).into());
+}
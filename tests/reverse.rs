@@ -0,0 +1,90 @@
+#![allow(non_camel_case_types, non_snake_case, non_upper_case_globals)]
+
+use enum_ffi_newtype::enum_ffi_rustify;
+
+// As generated by bindgen with `newtype_enum`
+#[enum_ffi_rustify(repr = "::core::ffi::c_uint", names, variants)]
+impl Foo {
+    /// The first variant
+    pub const FOO_A: Foo = Foo(0);
+    pub const FOO_B: Foo = Foo(5);
+}
+#[repr(transparent)]
+#[derive(Debug, Copy, Clone, Hash, PartialEq, Eq)]
+pub struct Foo(pub ::core::ffi::c_uint);
+
+#[enum_ffi_rustify(repr = "i32", rust_enum_name = "{}Rs", catch_all = "Other", keep_value, display)]
+impl Bar {
+    pub const BAR_A: Bar = Bar(-1);
+    pub const BAR_B: Bar = Bar(1);
+}
+#[repr(transparent)]
+#[derive(Debug, Copy, Clone, Hash, PartialEq, Eq)]
+pub struct Bar(pub i32);
+
+// As generated by bindgen with `constified_enum_module`
+#[enum_ffi_rustify]
+pub mod Baz {
+    pub type Type = ::core::ffi::c_int;
+    pub const BAZ_A: Type = 0;
+    pub const BAZ_B: Type = -3;
+}
+
+#[enum_ffi_rustify(newtype_name = "{}Ffi", exhaustive)]
+pub mod Qux {
+    pub type Type = u8;
+    pub const QUX_A: Type = 1;
+}
+
+#[test]
+fn test_newtype() {
+    assert_eq!(FooRustified::FOO_B, Foo::FOO_B.into());
+    assert_eq!(FooRustified::UnknownVariantFoo, Foo(17).into());
+    assert_eq!(Foo::FOO_A, FooRustified::FOO_A.into());
+    assert_eq!(Some("FOO_B"), Foo::FOO_B.name());
+    assert_eq!("The first variant", Foo::VARIANTS[0].doc);
+
+    assert_eq!(BarRs::Other(3), Bar(3).into());
+    assert_eq!(Bar(3), BarRs::Other(3).into());
+    assert_eq!("BAR_A", Bar::BAR_A.to_string());
+}
+
+#[test]
+fn test_module() {
+    assert_eq!(BazRustified::BAZ_B, BazNewtype(Baz::BAZ_B).into());
+    assert_eq!(BazRustified::UnknownVariantBaz, BazNewtype(7).into());
+    assert_eq!(Baz::BAZ_A, BazNewtype::from(BazRustified::BAZ_A).0);
+    assert_eq!("BazNewtype::BAZ_B", format!("{:?}", BazNewtype::BAZ_B));
+
+    assert_eq!(Ok(QuxRustified::QUX_A), QuxRustified::try_from(QuxFfi(Qux::QUX_A)));
+    assert!(QuxRustified::try_from(QuxFfi(0)).is_err());
+}
+
+// As generated by bindgen with `newtype_enum`, with one `impl` block per constant
+#[enum_ffi_rustify(names)]
+mod bindings {
+    impl Quux {
+        pub const QUUX_A: Quux = Quux(0);
+    }
+    impl Quux {
+        pub const QUUX_B: Quux = Quux(4);
+    }
+    impl Quux {
+        pub const QUUX_DUPLICATED: Quux = Quux(4);
+    }
+    #[repr(transparent)]
+    #[derive(Debug, Copy, Clone, Hash, PartialEq, Eq)]
+    pub struct Quux(pub ::core::ffi::c_uint);
+}
+
+#[test]
+fn test_newtype_impl_blocks() {
+    use bindings::*;
+
+    assert_eq!(QuuxRustified::QUUX_B, Quux::QUUX_DUPLICATED.into());
+    assert_eq!(QuuxRustified::QUUX_A, Quux(0).into());
+    assert_eq!(QuuxRustified::UnknownVariantQuux, Quux(1).into());
+    assert_eq!(Quux::QUUX_B, QuuxRustified::QUUX_B.into());
+    assert_eq!(Some(Quux::QUUX_B), Quux::from_name("QUUX_DUPLICATED"));
+    assert_eq!(Some("QUUX_B"), Quux::QUUX_DUPLICATED.name());
+}
@@ -0,0 +1,91 @@
+#![cfg(feature = "serde")]
+
+use serde_json::json;
+
+#[enum_ffi_newtype::enum_ffi(serde)]
+#[repr(i32)]
+#[derive(Debug, PartialEq)]
+enum Foo {
+    VariantA,
+    VariantB = 5,
+}
+
+#[enum_ffi_newtype::enum_ffi(serde = "name", name_case_insensitive)]
+#[repr(u8)]
+#[derive(Debug, PartialEq)]
+enum FooName {
+    VariantA,
+    VariantB,
+}
+
+#[enum_ffi_newtype::enum_ffi(serde = "number", non_zero, catch_all = "Unknown", keep_value)]
+#[repr(u16)]
+#[derive(Debug, PartialEq)]
+enum FooNumber {
+    VariantA = 1,
+    VariantB,
+}
+
+#[enum_ffi_newtype::enum_ffi(serde = "name")]
+#[repr(u8)]
+#[derive(Debug, PartialEq)]
+enum FooSkip {
+    VariantA,
+    #[enum_ffi(skip)]
+    Uninitialized,
+    #[enum_ffi(skip = 0)]
+    Default,
+}
+
+#[enum_ffi_newtype::enum_ffi(serde = "number", exhaustive)]
+#[repr(u8)]
+#[derive(Debug, PartialEq)]
+enum FooExhaustive {
+    VariantA,
+}
+
+#[test]
+fn test_name_or_number() {
+    assert_eq!(json!("VariantB"), serde_json::to_value(Foo::VariantB).unwrap());
+    assert_eq!(json!(-3), serde_json::to_value(Foo(-3)).unwrap());
+    assert_eq!(json!("VariantA"), serde_json::to_value(FooRustified::VariantA).unwrap());
+
+    assert_eq!(Foo::VariantB, serde_json::from_value(json!("VariantB")).unwrap());
+    assert_eq!(Foo::VariantB, serde_json::from_value(json!(5)).unwrap());
+    assert_eq!(Foo(-3), serde_json::from_value(json!(-3)).unwrap());
+    assert_eq!(FooRustified::VariantB, serde_json::from_value(json!(5)).unwrap());
+    assert_eq!(FooRustified::UnknownVariantFoo, serde_json::from_value(json!(17)).unwrap());
+    assert!(serde_json::from_value::<Foo>(json!("VariantC")).is_err());
+    assert!(serde_json::from_value::<Foo>(json!(1u64 << 40)).is_err());
+}
+
+#[test]
+fn test_name() {
+    assert_eq!(json!("VariantB"), serde_json::to_value(FooName::VariantB).unwrap());
+    assert!(serde_json::to_value(FooName(7)).is_err());
+    assert!(serde_json::to_value(FooNameRustified::UnknownVariantFooName).is_err());
+    assert_eq!(FooNameRustified::VariantA, serde_json::from_value(json!("varianta")).unwrap());
+    assert!(serde_json::from_value::<FooName>(json!(0)).is_err());
+}
+
+#[test]
+fn test_number() {
+    assert_eq!(json!(2), serde_json::to_value(FooNumber::VariantB).unwrap());
+    let unknown = FooNumberRustified::Unknown(core::num::NonZero::new(9).unwrap());
+    assert_eq!(json!(9), serde_json::to_value(&unknown).unwrap());
+    assert_eq!(unknown, serde_json::from_value(json!(9)).unwrap());
+    assert_eq!(FooNumberRustified::VariantA, serde_json::from_value(json!(1)).unwrap());
+    assert!(serde_json::from_value::<FooNumber>(json!(0)).is_err());
+    assert!(serde_json::from_value::<FooNumber>(json!("VariantA")).is_err());
+
+    assert_eq!(FooExhaustiveRustified::VariantA, serde_json::from_value(json!(0)).unwrap());
+    let err = serde_json::from_value::<FooExhaustiveRustified>(json!(3)).unwrap_err();
+    assert_eq!("unknown `FooExhaustive` value: 3", err.to_string());
+}
+
+#[test]
+fn test_skip() {
+    assert_eq!(json!("VariantA"), serde_json::to_value(FooSkipRustified::Default).unwrap());
+    let err = serde_json::to_value(FooSkipRustified::Uninitialized).unwrap_err();
+    assert_eq!("`FooSkipRustified::Uninitialized` has no `FooSkip` value", err.to_string());
+}
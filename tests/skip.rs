@@ -0,0 +1,48 @@
+use core::num::NonZero;
+
+#[enum_ffi_newtype::enum_ffi(display, names, variants)]
+#[repr(u8)]
+#[derive(Debug, PartialEq)]
+enum Foo {
+    VariantA,
+    VariantB,
+    #[enum_ffi(skip)]
+    Uninitialized,
+}
+
+#[enum_ffi_newtype::enum_ffi(non_zero, catch_all = "Unknown")]
+#[repr(i32)]
+#[derive(Debug, PartialEq)]
+enum FooError {
+    NotReady = 1,
+    #[enum_ffi(skip = -1)]
+    Unknown = 0,
+    #[enum_ffi(skip = 1)]
+    Pending = 2,
+}
+
+#[test]
+fn test_fallible() {
+    assert_eq!(Ok(Foo::VariantB), FooRustified::VariantB.try_to_ffi());
+    assert_eq!(Err(TryFromFooRustifiedError("Uninitialized")), Foo::try_from(FooRustified::Uninitialized));
+    assert_eq!(
+        "`FooRustified::Uninitialized` has no `Foo` value",
+        FooRustified::Uninitialized.try_to_ffi().unwrap_err().to_string(),
+    );
+    // The discriminant of the skipped variant is not a known value.
+    assert_eq!(FooRustified::UnknownVariantFoo, Foo(2).into());
+    assert_eq!(&[Foo::VariantA, Foo::VariantB], Foo::KNOWN);
+    assert_eq!(&[FooRustified::VariantA, FooRustified::VariantB], FooRustified::ALL);
+    assert_eq!("Uninitialized", FooRustified::Uninitialized.name());
+    assert_eq!("Uninitialized", FooRustified::Uninitialized.to_string());
+}
+
+#[test]
+fn test_value() {
+    const PENDING: FooError = FooErrorRustified::Pending.to_ffi();
+    assert_eq!(FooError::NotReady, PENDING);
+    assert_eq!(FooErrorRustified::NotReady, PENDING.into());
+    assert_eq!(FooError(NonZero::new(-1).unwrap()), FooErrorRustified::Unknown.into());
+    assert_eq!(FooErrorRustified::Unknown, FooError(NonZero::new(-1).unwrap()).into());
+    assert_eq!(FooErrorRustified::Unknown, FooError(NonZero::new(2).unwrap()).into());
+}
@@ -0,0 +1,52 @@
+use enum_ffi_newtype::ffi_try;
+
+#[enum_ffi_newtype::enum_ffi(status, success = "Ok", cbindgen(screaming_snake_case), variants)]
+#[repr(i32)]
+#[derive(Debug, PartialEq)]
+pub enum FooStatus {
+    Ok,
+    NotReady,
+    TimedOut = -1,
+}
+
+#[enum_ffi_newtype::enum_ffi(status, variants)]
+#[repr(u8)]
+#[derive(Debug, PartialEq)]
+enum BarStatus {
+    Failed = 1,
+    Success = 0,
+}
+
+extern "C" fn foo_init(fail: bool) -> i32 {
+    if fail { -1 } else { 0 }
+}
+
+fn init(fail: bool) -> Result<u32, FooStatusRustified> {
+    ffi_try!(FooStatus, foo_init(fail));
+    Ok(7)
+}
+
+#[test]
+fn test_result() {
+    assert_eq!(Ok(()), FooStatus::into_result(0));
+    assert_eq!(Err(FooStatus::NotReady), FooStatus::into_result(1));
+    assert_eq!(-1, FooStatus::from_result(Err(FooStatus::TimedOut)));
+    assert_eq!(FooStatus::SUCCESS, FooStatus::from_result(Ok(())));
+    assert_eq!(&[FooStatus::NotReady, FooStatus::TimedOut], FooStatus::KNOWN);
+    assert_eq!(FooStatusRustified::UnknownVariantFooStatus, FooStatus::into_result(5).unwrap_err().into());
+    assert_eq!(Err(BarStatus::Failed), BarStatus::into_result(1));
+    assert_eq!(&[BarStatusRustified::Failed], BarStatusRustified::ALL);
+    assert_eq!(size_of::<i32>(), size_of::<Result<(), FooStatus>>());
+}
+
+#[test]
+fn test_ffi_try() {
+    assert_eq!(Ok(7), init(false));
+    assert_eq!(Err(FooStatusRustified::TimedOut), init(true));
+}
+
+#[test]
+fn test_cbindgen() {
+    assert_eq!(0, FOO_STATUS_OK);
+    assert_eq!(1, FOO_STATUS_NOT_READY);
+}
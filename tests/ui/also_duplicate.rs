@@ -0,0 +1,9 @@
+#[enum_ffi_newtype::enum_ffi]
+#[repr(u8)]
+enum Foo {
+    VariantA,
+    #[enum_ffi(also = [0])]
+    VariantB,
+}
+
+fn main() {}
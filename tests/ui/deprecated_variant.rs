@@ -0,0 +1,14 @@
+#![deny(deprecated)]
+
+#[enum_ffi_newtype::enum_ffi]
+#[repr(u32)]
+enum Foo {
+    Current,
+    #[deprecated = "use `Current` instead"]
+    Old,
+}
+
+fn main() {
+    let _ = Foo::Current;
+    let _ = Foo::Old;
+}
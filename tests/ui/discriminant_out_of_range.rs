@@ -0,0 +1,32 @@
+#[enum_ffi_newtype::enum_ffi]
+#[repr(u8)]
+enum Explicit {
+    Variant = 300,
+}
+
+#[enum_ffi_newtype::enum_ffi]
+#[repr(u8)]
+enum Implicit {
+    Variant = 0xFF,
+    Overflow,
+}
+
+#[enum_ffi_newtype::enum_ffi]
+#[repr(u32)]
+enum Negative {
+    Variant = -1,
+}
+
+#[enum_ffi_newtype::enum_ffi]
+#[repr(u8)]
+enum Shifted {
+    Variant = 1 << 8,
+}
+
+#[enum_ffi_newtype::enum_ffi]
+#[repr(u32)]
+enum Suffix {
+    Variant = 5u8,
+}
+
+fn main() {}
@@ -0,0 +1,15 @@
+#[enum_ffi_newtype::enum_ffi(exhaustive)]
+#[repr(u32)]
+enum Foo {
+    Variant,
+}
+
+#[enum_ffi_newtype::enum_ffi(exhaustive, catch_all = "Unknown")]
+#[repr(u32)]
+enum Bar {
+    Variant,
+}
+
+fn main() {
+    let _: FooRustified = Foo(0).into();
+}
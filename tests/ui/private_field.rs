@@ -0,0 +1,12 @@
+mod ffi {
+    #[enum_ffi_newtype::enum_ffi(private_field)]
+    #[repr(u32)]
+    pub enum Foo {
+        Variant,
+    }
+}
+
+fn main() {
+    let _ = ffi::Foo(3);
+    let _ = ffi::Foo::Variant.0;
+}
@@ -0,0 +1,13 @@
+#[enum_ffi_newtype::enum_ffi]
+#[repr(u32, align(8))]
+enum Foo {
+    Variant,
+}
+
+#[enum_ffi_newtype::enum_ffi]
+#[repr(u8, u16)]
+enum Bar {
+    Variant,
+}
+
+fn main() {}
@@ -0,0 +1,14 @@
+#[enum_ffi_newtype::enum_ffi(status)]
+#[repr(u8)]
+enum FooStatus {
+    Failed = 1,
+    TimedOut,
+}
+
+#[enum_ffi_newtype::enum_ffi(status, success = "Ok")]
+#[repr(u8)]
+enum BarStatus {
+    Ok = 1,
+}
+
+fn main() {}
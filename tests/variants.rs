@@ -0,0 +1,73 @@
+#[enum_ffi_newtype::enum_ffi(names, variants)]
+#[repr(i16)]
+#[derive(Debug, PartialEq)]
+enum Foo {
+    /// The first variant
+    ///
+    /// With details.
+    VariantA = 3,
+    VariantB = -2,
+    VariantC = 7,
+}
+
+#[enum_ffi_newtype::enum_ffi(non_zero, catch_all = "Unknown", variants)]
+#[repr(u8)]
+#[derive(Debug, PartialEq)]
+enum FooNonZero {
+    VariantA = 2,
+    Unknown = 0xff,
+    VariantB = 1,
+}
+
+/// Variants named like the generated items, which take precedence over them
+#[enum_ffi_newtype::enum_ffi(variants)]
+#[repr(u32)]
+#[derive(Debug, PartialEq)]
+#[allow(non_camel_case_types, clippy::upper_case_acronyms)]
+enum Scope {
+    NONE = 0,
+    USER = 1,
+    ALL = 2,
+    #[enum_ffi(alias = "KNOWN")]
+    VARIANT_COUNT = 3,
+}
+
+#[test]
+fn test_known() {
+    assert_eq!(&[Foo::VariantA, Foo::VariantB, Foo::VariantC], Foo::KNOWN);
+    assert_eq!(3, Foo::VARIANT_COUNT);
+    assert_eq!(Foo::VariantB, Foo::MIN_KNOWN);
+    assert_eq!(Foo::VariantC, Foo::MAX_KNOWN);
+
+    assert_eq!(3, FooNonZero::VARIANT_COUNT);
+    assert_eq!(FooNonZero::VariantB, FooNonZero::MIN_KNOWN);
+    assert_eq!(FooNonZero::Unknown, FooNonZero::MAX_KNOWN);
+}
+
+#[test]
+fn test_all() {
+    assert_eq!(&[FooRustified::VariantA, FooRustified::VariantB, FooRustified::VariantC], FooRustified::ALL);
+    assert_eq!(3, FooRustified::VARIANT_COUNT);
+    let names: Vec<_> = FooRustified::iter().map(FooRustified::name).collect();
+    assert_eq!(vec!["VariantA", "VariantB", "VariantC"], names);
+    assert!(!FooNonZeroRustified::ALL.contains(&FooNonZeroRustified::Unknown));
+    assert_eq!(2, FooNonZeroRustified::VARIANT_COUNT);
+}
+
+#[test]
+fn test_name_clash() {
+    assert_eq!(Scope(2), Scope::ALL);
+    assert_eq!(Scope(3), Scope::KNOWN);
+    assert_eq!(Scope(3), Scope::VARIANT_COUNT);
+    assert_eq!(Scope::VARIANT_COUNT, Scope::MAX_KNOWN);
+    assert_eq!(4, Scope::VARIANTS.len());
+    assert_eq!(ScopeRustified::ALL, Scope::ALL.into());
+    assert_eq!(4, ScopeRustified::iter().count());
+}
+
+#[test]
+fn test_variant_info() {
+    assert_eq!(FooVariantInfo { name: "VariantA", value: 3, doc: "The first variant\n\nWith details." }, Foo::VARIANTS[0]);
+    assert_eq!(FooVariantInfo { name: "VariantB", value: -2, doc: "" }, Foo::VARIANTS[1]);
+    assert_eq!(core::num::NonZero::new(0xff).unwrap(), FooNonZero::VARIANTS[1].value);
+}